@@ -0,0 +1,38 @@
+//! Compares packing large messages with fixed and with growing chunk capacities:
+//! `cargo bench --bench message_pack`.
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use raio::messaging::message::Message;
+
+const ITERATIONS: u32 = 200;
+
+async fn pack(size: usize, grow: bool) -> (usize, Duration) {
+    let payload = vec![0x42u8; size];
+    let mut target = Vec::with_capacity(2 * size);
+    let mut chunks = 0;
+
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        let mut message = Message::new_alloc(1, 1400).grow_chunks(grow);
+        message.write_all(&payload).unwrap();
+        message.pack(&mut target).await.unwrap();
+
+        chunks = message.chunks().len();
+        target.clear();
+    }
+
+    (chunks, started.elapsed() / ITERATIONS)
+}
+
+#[async_std::main]
+async fn main() {
+    for size in &[100, 10_000, 1_000_000] {
+        for grow in &[false, true] {
+            let (chunks, duration) = pack(*size, *grow).await;
+            println!(
+                "{:>9} bytes, growing chunks: {:<5} => {:>4} chunks, {:?} per message",
+                size, grow, chunks, duration);
+        }
+    }
+}
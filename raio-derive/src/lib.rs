@@ -0,0 +1,86 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, GenericArgument, PathArguments, Type};
+
+/// Derives `FromRecord` for a struct with named fields. Every field is extracted from the record
+/// field of the same name; fields of type `Option<T>` may be missing or `null` in the record. A
+/// value of another type fails with `ConversionError::InvalidValue`.
+#[proc_macro_derive(FromRecord)]
+pub fn derive_from_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return error(name, "FromRecord can only be derived for structs with named fields"),
+        },
+        _ => return error(name, "FromRecord can only be derived for structs"),
+    };
+
+    let extractions = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let key = ident.to_string();
+        match option_inner(&field.ty) {
+            Some(inner) => quote! {
+                #ident: match record.try_extract::<#inner>(#key) {
+                    Ok(value) => value,
+                    Err(::raio::client::record_result::ConversionError::MissingField(_)) => None,
+                    Err(e) => return Err(e.into()),
+                }
+            },
+            None => {
+                let ty = &field.ty;
+                quote! {
+                    #ident: match record.try_extract::<#ty>(#key) {
+                        Ok(Some(value)) => value,
+                        Ok(None) | Err(::raio::client::record_result::ConversionError::MissingField(_)) =>
+                            return Err(::raio::client::error::ClientError::MissingRecordField(String::from(#key))),
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+            }
+        }
+    });
+
+    let expanded = quote! {
+        impl #impl_generics ::raio::client::record_result::FromRecord for #name #ty_generics #where_clause {
+            fn from_record(mut record: ::raio::client::record_result::RecordResult)
+                -> Result<Self, ::raio::client::error::ClientError> {
+                Ok(#name {
+                    #(#extractions,)*
+                })
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// The `T` of an `Option<T>`, if `ty` is one.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(p) if p.qself.is_none() => &p.path,
+        _ => return None,
+    };
+
+    let segment = path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn error(name: &syn::Ident, message: &str) -> TokenStream {
+    let error: TokenStream2 = syn::Error::new(name.span(), message).to_compile_error();
+    error.into()
+}
@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use async_std::task::block_on;
+
+use crate::client::{self, ClientConfig};
+use crate::client::auth::AuthMethod;
+use crate::client::auto_commit::{AutoCommit, AutoCommitResult};
+use crate::client::error::ClientError;
+use crate::client::summary::{ResultSummary, ServerInfo};
+use crate::client::transaction::{self, CommitOutcome, TransactionResult};
+use crate::connectivity::pool::PoolStatus;
+use crate::messaging::bookmark::Bookmark;
+use crate::messaging::commit_prepare::CommitPrepare;
+use crate::messaging::query::{ParamMap, Query};
+
+/// A blocking facade over the asynchronous [`Client`](crate::client::Client), for programs
+/// which do not run an executor, like scripts and command line tools. Every call blocks the
+/// current thread until it is done; pooling, encryption and all protocol versions work as on
+/// the asynchronous client:
+/// ```no_run
+/// # use raio::blocking::Client;
+/// # use raio::client::ClientConfig;
+/// # use raio::client::auth::Basic;
+/// # use raio::messaging::query::Query;
+/// # fn main() -> Result<(), raio::client::error::ClientError> {
+/// let client = Client::create("localhost:7687", Basic::new("neo4j", "neo4j"), ClientConfig::default("cli", "0.1"));
+///
+/// let result = client.query(&Query::new("RETURN 1 as x"))?;
+/// assert_eq!(result.records()[0].get_field_typed("x"), Some(&1));
+/// # Ok(())
+/// # }
+/// ```
+/// Must not be used from within an asynchronous task.
+#[derive(Clone)]
+pub struct Client {
+    inner: client::Client,
+}
+
+impl Client {
+    /// Creates a client, see [`Client::create`](crate::client::Client::create).
+    pub fn create<A: AuthMethod>(endpoint: &str, auth: A, config: ClientConfig) -> Self {
+        Client {
+            inner: client::Client::create(endpoint, auth, config),
+        }
+    }
+
+    /// Creates a client from a URI, see [`Client::from_uri`](crate::client::Client::from_uri).
+    pub fn from_uri<A: AuthMethod>(uri: &str, auth: A, config: ClientConfig) -> Result<Self, ClientError> {
+        Ok(Client {
+            inner: client::Client::from_uri(uri, auth, config)?,
+        })
+    }
+
+    /// The asynchronous client behind this one.
+    pub fn inner(&self) -> &client::Client {
+        &self.inner
+    }
+
+    pub fn query(&self, query: &Query) -> Result<AutoCommitResult, ClientError> {
+        block_on(self.inner.query(query))
+    }
+
+    pub fn query_after(&self, query: &Query, before: Bookmark) -> Result<AutoCommitResult, ClientError> {
+        block_on(self.inner.query_after(query, before))
+    }
+
+    pub fn run(&self, auto_commit: &AutoCommit<'_>) -> Result<AutoCommitResult, ClientError> {
+        block_on(self.inner.run(auto_commit))
+    }
+
+    pub fn run_batch<I, M>(&self, query: &Query, rows: I, batch_size: usize) -> Result<usize, ClientError>
+        where I: IntoIterator<Item = M>, M: Into<ParamMap> {
+        block_on(self.inner.run_batch(query, rows, batch_size))
+    }
+
+    pub fn prepare(&self, statement: &str) -> Query {
+        self.inner.prepare(statement)
+    }
+
+    pub fn begin(&self, settings: CommitPrepare) -> Result<Transaction, ClientError> {
+        Ok(Transaction {
+            inner: block_on(self.inner.begin(settings))?,
+        })
+    }
+
+    pub fn warm_up(&self) -> Result<(), ClientError> {
+        block_on(self.inner.warm_up())
+    }
+
+    pub fn server_info(&self) -> Result<ServerInfo, ClientError> {
+        block_on(self.inner.server_info())
+    }
+
+    pub fn pool_status(&self) -> PoolStatus {
+        self.inner.pool_status()
+    }
+
+    pub fn close(&self, timeout: Duration) -> Result<(), ClientError> {
+        block_on(self.inner.close(timeout))
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+}
+
+/// A blocking facade over an explicit [`Transaction`](crate::client::transaction::Transaction).
+pub struct Transaction {
+    inner: transaction::Transaction,
+}
+
+impl Transaction {
+    pub fn run(&mut self, query: &Query) -> Result<TransactionResult, ClientError> {
+        block_on(self.inner.run(query))
+    }
+
+    pub fn execute(&mut self, query: &Query) -> Result<ResultSummary, ClientError> {
+        block_on(self.inner.execute(query))
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.inner.is_open()
+    }
+
+    pub fn commit(self) -> Result<CommitOutcome, ClientError> {
+        block_on(self.inner.commit())
+    }
+
+    pub fn rollback(self) -> Result<(), ClientError> {
+        block_on(self.inner.rollback())
+    }
+}
@@ -1,5 +1,9 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
 /// The general form of authentication data. It Is mainly used by
 /// [`AuthMethod`](crate::client::auth::AuthMethod).
+#[derive(Debug, Clone, PartialEq)]
 pub struct AuthData {
     pub scheme: String,
     pub principal: String,
@@ -9,6 +13,33 @@ pub trait AuthMethod {
     fn into_auth_data(self) -> AuthData;
 }
 
+impl AuthMethod for AuthData {
+    fn into_auth_data(self) -> AuthData {
+        self
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Auth provider failed: {0}")]
+/// An error of an [`AuthProvider`](crate::client::auth::AuthProvider) which could not provide a
+/// token.
+pub struct AuthProviderError(pub String);
+
+#[async_trait]
+/// Provides the authentication of new connections. It is asked whenever a connection gets
+/// authenticated, hence it can hand out refreshed tokens for short-lived credentials. Static
+/// credentials, i.e. `AuthData`, always provide themselves.
+pub trait AuthProvider: Send + Sync {
+    async fn get_token(&self) -> Result<AuthData, AuthProviderError>;
+}
+
+#[async_trait]
+impl AuthProvider for AuthData {
+    async fn get_token(&self) -> Result<AuthData, AuthProviderError> {
+        Ok(self.clone())
+    }
+}
+
 /// The basic auth method, which uses a user name and a password.
 /// ```
 /// # use raio::client::auth::{Basic, AuthMethod};
@@ -1,10 +1,19 @@
-use crate::messaging::request::{Run};
-use crate::messaging::commit_prepare::CommitPrepare;
+use crate::messaging::request::{Run, Amount};
+use crate::messaging::commit_prepare::{CommitPrepare, CommitMode};
 use crate::messaging::query::Query;
 use crate::messaging::bookmark::Bookmark;
-use crate::client::record_result::RecordResult;
+use crate::client::record_result::{RecordResult, FromRecord};
 use crate::messaging::response::{Success, Record};
 use crate::client::error::ClientError;
+use crate::client::summary::ResultSummary;
+use crate::client::graph::Graph;
+use crate::client::columns::Columns;
+use crate::client::csv::{write_csv, CsvOptions};
+#[cfg(feature = "arrow")]
+use crate::client::record_batch::RecordBatchError;
+use futures::Stream;
+use std::io::{self, Write};
+use std::sync::Arc;
 
 /// A thin wrapper around a `RUN` message in an auto-commit context. Can be used to prepare a
 /// common auto-commit, i.e. a query and a few commit options.
@@ -24,7 +33,9 @@ use crate::client::error::ClientError;
 /// ```
 /// The `run` function of a [`Client`](crate::client::Client) runs an `AutoCommit`.
 pub struct AutoCommit<'a> {
-    run: Run<'a>
+    run: Run<'a>,
+    fetch_size: Option<Amount>,
+    idempotent: bool,
 }
 
 impl<'a> AutoCommit<'a> {
@@ -33,7 +44,9 @@ impl<'a> AutoCommit<'a> {
     pub fn new(query: &'a Query) -> Self {
         let run = Run::new(query);
         AutoCommit {
-            run
+            run,
+            fetch_size: None,
+            idempotent: false,
         }
     }
 
@@ -43,6 +56,45 @@ impl<'a> AutoCommit<'a> {
         self.run.commit_prepare()
     }
 
+    /// Sets the number of records pulled at once, overriding the fetch size of the client.
+    pub fn set_fetch_size(&mut self, n: Amount) -> &mut Self {
+        self.fetch_size = Some(n);
+        self
+    }
+
+    pub fn fetch_size(&self) -> Option<Amount> {
+        self.fetch_size
+    }
+
+    /// Marks the query as safe to run more than once, such that it is run again on a fresh
+    /// connection if its connection broke. Queries in read mode are considered idempotent anyway.
+    pub fn set_idempotent(&mut self, idempotent: bool) -> &mut Self {
+        self.idempotent = idempotent;
+        self
+    }
+
+    /// Runs the auto-commit in read mode, such that a routing client sends it to a reader, even
+    /// if the session it is run in is in write mode.
+    pub fn read(&mut self) -> &mut Self {
+        self.run.commit_prepare().set_mode(Some(CommitMode::Read));
+        self
+    }
+
+    /// Runs the auto-commit in write mode, such that a routing client sends it to a writer, even
+    /// if the session it is run in is in read mode.
+    pub fn write(&mut self) -> &mut Self {
+        self.run.commit_prepare().set_mode(Some(CommitMode::Write));
+        self
+    }
+
+    pub fn is_idempotent(&self) -> bool {
+        self.idempotent || self.run.mode() == Some(CommitMode::Read)
+    }
+
+    pub fn query(&self) -> &Query {
+        self.run.query()
+    }
+
     /// Return the `AutoCommit` as a request, which can be sent to the server.
     pub fn request(&self) -> &Run {
         &self.run
@@ -50,27 +102,47 @@ impl<'a> AutoCommit<'a> {
 }
 
 pub struct AutoCommitResult {
-    bookmark: Bookmark,
+    fields: Arc<[String]>,
+    bookmark: Option<Bookmark>,
     records: Vec<RecordResult>,
+    summary: ResultSummary,
 }
 
 impl AutoCommitResult {
-    /// Creates a new `CommitResult` from a final `SUCCESS` message, and a list of `RECORD`s.
-    pub fn new(fields: &[String], stream_end: Success, records: Vec<Record>) -> Result<Self, ClientError> {
-        let bookmark = Bookmark::from_success(stream_end)?;
-
-        // build up record results:
-        let records = RecordResult::from_results(fields, records)?;
+    /// Creates a new `CommitResult` from the `SUCCESS` of the `RUN`, a final `SUCCESS` message,
+    /// and a list of `RECORD`s.
+    pub fn new(fields: &[String], mut stream_begin: Success, mut stream_end: Success, records: Vec<Record>) -> Result<Self, ClientError> {
+        let summary = ResultSummary::from_success(&mut stream_begin, &mut stream_end);
+        let bookmark = Bookmark::from_success(stream_end);
 
+        // build up record results, sharing the field names:
+        let fields: Arc<[String]> = Arc::from(fields);
+        let records = records
+            .into_iter()
+            .map(|r| RecordResult::with_fields(fields.clone(), r))
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(AutoCommitResult {
+            fields,
             bookmark,
             records,
+            summary,
         })
     }
 
-    pub fn bookmark(&self) -> &Bookmark {
-        &self.bookmark
+    pub fn summary(&self) -> &ResultSummary {
+        &self.summary
+    }
+
+    /// The bookmark of the auto-commit; the server might not send one, e.g. for read-only
+    /// queries.
+    pub fn bookmark(&self) -> Option<&Bookmark> {
+        self.bookmark.as_ref()
+    }
+
+    /// The field names of the records, also if there are no records.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
     }
 
     pub fn records(&self) -> &Vec<RecordResult> {
@@ -80,4 +152,46 @@ impl AutoCommitResult {
     pub fn into_records(self) -> Vec<RecordResult> {
         self.records
     }
+
+    /// Turns the records into a stream with the same items as a
+    /// [`RecordStream`](crate::client::record_stream::RecordStream), such that code consuming
+    /// records works on both. To process records while they arrive, use
+    /// [`Client::query_stream`](crate::client::Client::query_stream) instead, which pulls
+    /// lazily.
+    pub fn into_stream(self) -> impl Stream<Item = Result<RecordResult, ClientError>> {
+        futures::stream::iter(self.records.into_iter().map(Ok))
+    }
+
+    /// Collects the distinct nodes and relationships of all records.
+    pub fn graph(&self) -> Graph {
+        Graph::from_records(&self.records)
+    }
+
+    /// Transposes the records into one column per field, see
+    /// [`Columns`](crate::client::columns::Columns).
+    pub fn into_columns(self) -> Columns {
+        Columns::from_records(self.fields, self.records)
+    }
+
+    /// Converts the records into an arrow `RecordBatch`; copies all values, which
+    /// `into_columns().to_record_batch()` avoids.
+    #[cfg(feature = "arrow")]
+    pub fn to_record_batch(&self) -> Result<::arrow::record_batch::RecordBatch, RecordBatchError> {
+        Columns::from_records(self.fields.clone(), self.records.clone()).to_record_batch()
+    }
+
+    /// Writes the records as comma separated values with a header row, see
+    /// [`write_csv`](crate::client::csv::write_csv).
+    pub fn write_csv<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        self.write_csv_with(w, &CsvOptions::default())
+    }
+
+    pub fn write_csv_with<W: Write>(&self, w: &mut W, options: &CsvOptions) -> io::Result<()> {
+        write_csv(w, &self.fields, &self.records, options)
+    }
+
+    /// Maps every record into a `T`, failing on the first record which does not fit.
+    pub fn into_typed<T: FromRecord>(self) -> Result<Vec<T>, ClientError> {
+        self.records.into_iter().map(T::from_record).collect()
+    }
 }
@@ -0,0 +1,55 @@
+use std::sync::Mutex;
+
+use crate::messaging::bookmark::Bookmark;
+
+/// Keeps the bookmarks which queries have to wait for, such that several sessions, or even
+/// several services sharing a store, see each other's changes. A session sends the bookmarks of
+/// its manager with every query and hands the resulting bookmark back to it:
+/// ```
+/// # use raio::client::bookmark_manager::{BookmarkManager, InMemoryBookmarkManager};
+/// let manager = InMemoryBookmarkManager::new(Vec::new());
+/// assert!(manager.bookmarks().is_empty());
+/// ```
+pub trait BookmarkManager: Send + Sync {
+    /// The bookmarks the next query has to wait for.
+    fn bookmarks(&self) -> Vec<Bookmark>;
+
+    /// Replaces the bookmarks `previous`, which a finished query waited for, by the bookmark
+    /// `new` of that query.
+    fn update_bookmarks(&self, previous: &[Bookmark], new: &Bookmark);
+}
+
+#[derive(Debug, Default)]
+/// A `BookmarkManager` which keeps the bookmarks in memory, to be shared by the sessions of a
+/// process.
+pub struct InMemoryBookmarkManager {
+    bookmarks: Mutex<Vec<Bookmark>>,
+}
+
+impl InMemoryBookmarkManager {
+    pub fn new(initial: Vec<Bookmark>) -> Self {
+        InMemoryBookmarkManager {
+            bookmarks: Mutex::new(initial),
+        }
+    }
+}
+
+impl BookmarkManager for InMemoryBookmarkManager {
+    fn bookmarks(&self) -> Vec<Bookmark> {
+        self.bookmarks.lock().unwrap().clone()
+    }
+
+    fn update_bookmarks(&self, previous: &[Bookmark], new: &Bookmark) {
+        let mut bookmarks = self.bookmarks.lock().unwrap();
+        bookmarks.retain(|b| !previous.contains(b));
+        if !bookmarks.contains(new) {
+            bookmarks.push(new.clone());
+        }
+    }
+}
+
+impl std::fmt::Debug for dyn BookmarkManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("BookmarkManager")
+    }
+}
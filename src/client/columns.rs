@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use packs::Value;
+use packs::std_structs::StdStruct;
+
+use crate::client::convert::FromValue;
+use crate::client::record_result::{ConversionError, RecordResult};
+#[cfg(feature = "arrow")]
+use crate::client::record_batch::{record_batch, RecordBatchError};
+
+#[derive(Debug, Clone, PartialEq)]
+/// The values of a result by column instead of by row, e.g. for aggregations or for handing the
+/// result over to a data frame:
+/// ```
+/// # use std::sync::Arc;
+/// # use raio::client::columns::Columns;
+/// # use raio::client::record_result::RecordResult;
+/// # use raio::messaging::response::Record;
+/// # use packs::Value;
+/// let fields: Arc<[String]> = Arc::from(vec![String::from("name"), String::from("age")]);
+/// let rows = vec![
+///     Record { data: vec![Value::from("Alice"), Value::Integer(42)] },
+///     Record { data: vec![Value::from("Bob"), Value::Integer(23)] },
+/// ];
+/// let rows = rows.into_iter().map(|r| RecordResult::with_fields(fields.clone(), r).unwrap()).collect();
+/// let columns = Columns::from_records(fields, rows);
+///
+/// assert_eq!(columns.len(), 2);
+/// assert_eq!(columns.column("name"), Some(&[Value::from("Alice"), Value::from("Bob")][..]));
+/// assert_eq!(columns.typed::<i64>("age"), Ok(vec![42, 23]));
+/// assert!(columns.typed::<i64>("name").is_err());
+/// ```
+pub struct Columns {
+    fields: Arc<[String]>,
+    columns: Vec<Vec<Value<StdStruct>>>,
+    rows: usize,
+}
+
+impl Columns {
+    /// Transposes `records`, which all have the provided `fields`.
+    pub fn from_records(fields: Arc<[String]>, records: Vec<RecordResult>) -> Self {
+        let rows = records.len();
+        let mut columns: Vec<Vec<Value<StdStruct>>> =
+            fields.iter().map(|_| Vec::with_capacity(rows)).collect();
+
+        for record in records.into_iter() {
+            for (column, value) in columns.iter_mut().zip(record.into_values()) {
+                column.push(value);
+            }
+        }
+
+        Columns {
+            fields,
+            columns,
+            rows,
+        }
+    }
+
+    /// The field names, in the order of the columns.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// The number of rows.
+    pub fn len(&self) -> usize {
+        self.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    pub fn column(&self, field: &str) -> Option<&[Value<StdStruct>]> {
+        self.index_of(field).map(|i| self.columns[i].as_slice())
+    }
+
+    /// Converts every value of the column `field` into `T`, failing on the first which does not
+    /// fit; use `Option<T>` for columns containing `null`.
+    pub fn typed<T: FromValue>(&self, field: &str) -> Result<Vec<T>, ConversionError> {
+        let column = self.column(field)
+            .ok_or_else(|| ConversionError::MissingField(String::from(field)))?;
+
+        column
+            .iter()
+            .map(|value| T::from_value(value)
+                .map_err(|error| ConversionError::InvalidValue { field: String::from(field), error }))
+            .collect()
+    }
+
+    /// The pairs of field name and column, in the order of the columns.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[Value<StdStruct>])> {
+        self.fields.iter().map(String::as_str).zip(self.columns.iter().map(Vec::as_slice))
+    }
+
+    /// Converts the columns into an arrow `RecordBatch`, see
+    /// [`record_batch`](crate::client::record_batch::record_batch) for the column types.
+    #[cfg(feature = "arrow")]
+    pub fn to_record_batch(&self) -> Result<::arrow::record_batch::RecordBatch, RecordBatchError> {
+        record_batch(self.iter())
+    }
+
+    pub fn into_columns(self) -> Vec<Vec<Value<StdStruct>>> {
+        self.columns
+    }
+
+    fn index_of(&self, field: &str) -> Option<usize> {
+        self.fields.iter().position(|f| f == field)
+    }
+}
@@ -0,0 +1,144 @@
+use std::any::type_name;
+use std::convert::TryFrom;
+
+use packs::Value;
+use packs::std_structs::StdStruct;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ValueError {
+    #[error("A value of kind {actual} cannot be converted into {expected}")]
+    UnexpectedType { expected: &'static str, actual: &'static str },
+    #[error("{value} is out of range for {target}")]
+    OutOfRange { value: String, target: &'static str },
+}
+
+/// Types which can be converted from a bolt value. In contrast to `Extract`, integers and floats
+/// can be converted into any width, as long as the value fits:
+/// ```
+/// # use raio::client::convert::{FromValue, ValueError};
+/// # use packs::Value;
+/// assert_eq!(u8::from_value(&Value::Integer(200)), Ok(200));
+/// assert_eq!(
+///     u8::from_value(&Value::Integer(300)),
+///     Err(ValueError::OutOfRange { value: String::from("300"), target: "u8" }));
+/// assert_eq!(<Option<i32>>::from_value(&Value::Null), Ok(None));
+/// ```
+pub trait FromValue: Sized {
+    fn from_value(value: &Value<StdStruct>) -> Result<Self, ValueError>;
+}
+
+fn unexpected<T>(value: &Value<StdStruct>) -> ValueError {
+    ValueError::UnexpectedType {
+        expected: type_name::<T>(),
+        actual: value_kind(value),
+    }
+}
+
+macro_rules! integer_from_value {
+    ($($t:ty),+) => {
+        $(
+            impl FromValue for $t {
+                fn from_value(value: &Value<StdStruct>) -> Result<Self, ValueError> {
+                    match value {
+                        Value::Integer(i) => <$t>::try_from(*i).map_err(|_| ValueError::OutOfRange {
+                            value: i.to_string(),
+                            target: type_name::<$t>(),
+                        }),
+                        other => Err(unexpected::<$t>(other)),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+integer_from_value!(i8, i16, i32, u8, u16, u32, u64, usize);
+
+impl FromValue for i64 {
+    fn from_value(value: &Value<StdStruct>) -> Result<Self, ValueError> {
+        match value {
+            Value::Integer(i) => Ok(*i),
+            other => Err(unexpected::<i64>(other)),
+        }
+    }
+}
+
+impl FromValue for f64 {
+    fn from_value(value: &Value<StdStruct>) -> Result<Self, ValueError> {
+        match value {
+            Value::Float(f) => Ok(*f),
+            other => Err(unexpected::<f64>(other)),
+        }
+    }
+}
+
+/// Converts a float, rounding to the nearest `f32`. Finite floats beyond the range of `f32` are
+/// out of range.
+impl FromValue for f32 {
+    fn from_value(value: &Value<StdStruct>) -> Result<Self, ValueError> {
+        match value {
+            Value::Float(f) if f.is_finite() && f.abs() > f64::from(f32::MAX) =>
+                Err(ValueError::OutOfRange { value: f.to_string(), target: "f32" }),
+            Value::Float(f) => Ok(*f as f32),
+            other => Err(unexpected::<f32>(other)),
+        }
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Value<StdStruct>) -> Result<Self, ValueError> {
+        match value {
+            Value::Boolean(b) => Ok(*b),
+            other => Err(unexpected::<bool>(other)),
+        }
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: &Value<StdStruct>) -> Result<Self, ValueError> {
+        match value {
+            Value::String(s) => Ok(s.clone()),
+            other => Err(unexpected::<String>(other)),
+        }
+    }
+}
+
+/// `null` converts into `None`.
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &Value<StdStruct>) -> Result<Self, ValueError> {
+        match value {
+            Value::Null => Ok(None),
+            other => T::from_value(other).map(Some),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: &Value<StdStruct>) -> Result<Self, ValueError> {
+        match value {
+            Value::List(list) => list.iter().map(T::from_value).collect(),
+            other => Err(unexpected::<Vec<T>>(other)),
+        }
+    }
+}
+
+/// The name of the kind of a value, as used in conversion errors.
+#[allow(unreachable_patterns)]
+pub(crate) fn value_kind(value: &Value<StdStruct>) -> &'static str {
+    match value {
+        Value::Null => "Null",
+        Value::Boolean(_) => "Boolean",
+        Value::Integer(_) => "Integer",
+        Value::Float(_) => "Float",
+        Value::Bytes(_) => "Bytes",
+        Value::String(_) => "String",
+        Value::List(_) => "List",
+        Value::Dictionary(_) => "Dictionary",
+        Value::Structure(StdStruct::Node(_)) => "Node",
+        Value::Structure(StdStruct::Relationship(_)) => "Relationship",
+        Value::Structure(StdStruct::UnboundRelationship(_)) => "UnboundRelationship",
+        Value::Structure(StdStruct::Path(_)) => "Path",
+        _ => "Structure",
+    }
+}
@@ -0,0 +1,125 @@
+use std::io::{self, Write};
+
+use packs::Value;
+use packs::std_structs::StdStruct;
+
+use crate::client::record_result::RecordResult;
+
+#[derive(Debug, Clone, PartialEq)]
+/// How results are written as CSV, see
+/// [`AutoCommitResult::write_csv_with`](crate::client::auto_commit::AutoCommitResult::write_csv_with).
+pub struct CsvOptions {
+    pub delimiter: char,
+    pub null: String,
+    pub header: bool,
+}
+
+impl Default for CsvOptions {
+    /// Comma separated, with a header row; `null` is written as an empty field.
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: ',',
+            null: String::new(),
+            header: true,
+        }
+    }
+}
+
+impl CsvOptions {
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets how `null` is written, e.g. `NULL` or `\N`.
+    pub fn null(mut self, null: &str) -> Self {
+        self.null = String::from(null);
+        self
+    }
+
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+}
+
+/// Writes `records` as CSV, one line per record, preceded by a header row of `fields`. Fields
+/// containing the delimiter, quotes or line breaks are quoted, quotes within are doubled. Lists
+/// and maps are written like Cypher literals, bytes in hex and nodes, relationships and paths in
+/// their debug representation:
+/// ```
+/// # use raio::client::csv::{write_csv, CsvOptions};
+/// # use raio::client::record_result::RecordResult;
+/// # use raio::messaging::response::Record;
+/// # use packs::Value;
+/// let fields = vec![String::from("name"), String::from("tags")];
+/// let records = vec![
+///     Record { data: vec![Value::from("Smith, Jane"), Value::from(vec![Value::from("a"), Value::from("b")])] },
+///     Record { data: vec![Value::from("Say \"hi\""), Value::Null] },
+/// ];
+/// let rows = RecordResult::from_results(&fields, records).unwrap();
+///
+/// let mut csv = Vec::new();
+/// write_csv(&mut csv, &fields, &rows, &CsvOptions::default().null("NULL")).unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(csv).unwrap(),
+///     "name,tags\n\"Smith, Jane\",\"['a', 'b']\"\n\"Say \"\"hi\"\"\",NULL\n");
+/// ```
+pub fn write_csv<W: Write>(w: &mut W, fields: &[String], records: &[RecordResult], options: &CsvOptions) -> io::Result<()> {
+    if options.header {
+        write_line(w, fields.iter().map(String::as_str), options)?;
+    }
+
+    for record in records {
+        let cells: Vec<String> = record.values().iter().map(|v| cell(v, options)).collect();
+        write_line(w, cells.iter().map(String::as_str), options)?;
+    }
+
+    Ok(())
+}
+
+fn write_line<'a, W: Write, I: Iterator<Item = &'a str>>(w: &mut W, cells: I, options: &CsvOptions) -> io::Result<()> {
+    let mut line = String::new();
+    for (i, cell) in cells.enumerate() {
+        if i > 0 {
+            line.push(options.delimiter);
+        }
+
+        if cell.contains(&[options.delimiter, '"', '\n', '\r'][..]) {
+            line.push('"');
+            line.push_str(&cell.replace('"', "\"\""));
+            line.push('"');
+        } else {
+            line.push_str(cell);
+        }
+    }
+
+    line.push('\n');
+    w.write_all(line.as_bytes())
+}
+
+fn cell(value: &Value<StdStruct>, options: &CsvOptions) -> String {
+    match value {
+        Value::Null => options.null.clone(),
+        Value::String(s) => s.clone(),
+        other => literal(other),
+    }
+}
+
+/// A value as a Cypher literal, for values nested in lists and maps.
+fn literal(value: &Value<StdStruct>) -> String {
+    match value {
+        Value::Null => String::from("null"),
+        Value::Boolean(b) => b.to_string(),
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Bytes(bytes) => bytes.iter().map(|b| format!("{:02X}", b)).collect(),
+        Value::String(s) => format!("'{}'", s.replace('\'', "\\'")),
+        Value::List(list) =>
+            format!("[{}]", list.iter().map(literal).collect::<Vec<_>>().join(", ")),
+        Value::Dictionary(dict) =>
+            format!("{{{}}}", dict.iter().map(|(k, v)| format!("{}: {}", k, literal(v))).collect::<Vec<_>>().join(", ")),
+        Value::Structure(s) => format!("{:?}", s),
+    }
+}
@@ -1,4 +1,7 @@
+use crate::client::record_result::ConversionError;
 use crate::connectivity::connection::ConnectionError;
+use crate::connectivity::uri::UriError;
+use crate::messaging::response::Failure;
 use deadpool::managed::PoolError;
 use thiserror::Error;
 
@@ -14,17 +17,259 @@ pub enum ClientError {
     NoQidInformation,
     #[error("Connection pool timed out")]
     PoolTimeOut,
+    #[error("Too many requests are waiting for a connection")]
+    PoolExhausted,
     #[error("The number of fields does not match the number of result columns.")]
     FieldsToRecordMismatch,
-    #[error("Cannot extract bookmark from commit")]
-    NoBookmarkInformationInCommit,
     #[error("Stream still open after PULL all from last.")]
     StreamStillOpen,
+    #[error("No server available in the routing table for the requested mode")]
+    NoServerAvailable,
+    #[error("Invalid URI: {0}")]
+    UriError(#[from] UriError),
+    #[error("Record has no field '{0}' of the expected type")]
+    MissingRecordField(String),
+    #[error("Conversion error: {0}")]
+    ConversionError(#[from] ConversionError),
+    #[error("Client is closed")]
+    Closed,
+    #[error("Transaction is closed")]
+    TransactionClosed,
+    #[error("Server unavailable after repeated connection failures")]
+    ServerUnavailable,
+}
+
+impl ClientError {
+    /// Checks if the error is transient, i.e. if the same work might succeed when it is tried
+    /// again. This is the case for Neo4j's transient errors, for cluster role changes, for
+    /// broken connections and for unreachable servers.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::IOError(_) => true,
+            ClientError::ServerUnavailable => true,
+            ClientError::ConnectionError(ConnectionError::IOError(_)) => true,
+            ClientError::ConnectionError(ConnectionError::Timeout) => true,
+            ClientError::ConnectionError(ConnectionError::FailureResponse(e)) => e.is_retryable(),
+            ClientError::ConnectionError(ConnectionError::AuthenticationError(e)) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Checks if the error was caused by a broken connection, such that the connection cannot be
+    /// used any further.
+    pub fn is_connection_broken(&self) -> bool {
+        matches!(
+            self,
+            ClientError::IOError(_)
+            | ClientError::ConnectionError(ConnectionError::IOError(_))
+            | ClientError::ConnectionError(ConnectionError::Timeout))
+    }
+
+    /// Checks if the error was caused by an expired token or authorization, such that the same
+    /// work might succeed on a new connection, which is authenticated with a fresh token of the
+    /// auth provider.
+    pub fn is_auth_expired(&self) -> bool {
+        match self {
+            ClientError::ConnectionError(ConnectionError::FailureResponse(e)) => e.is_auth_expired(),
+            ClientError::ConnectionError(ConnectionError::AuthenticationError(e)) => e.needs_reauthentication(),
+            _ => false,
+        }
+    }
+
+    /// Checks if a write was sent to a server which is no longer the writer of the database, see
+    /// [`Neo4jError::is_not_a_leader`](crate::client::error::Neo4jError::is_not_a_leader).
+    pub fn is_not_a_leader(&self) -> bool {
+        matches!(self.neo4j_error(), Some(e) if e.is_not_a_leader())
+    }
+
+    /// The failure reported by the server, if this error is one.
+    pub fn neo4j_error(&self) -> Option<&Neo4jError> {
+        match self {
+            ClientError::ConnectionError(ConnectionError::FailureResponse(e)) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// The classification of a Neo4j status code, i.e. its second part.
+pub enum Classification {
+    ClientError,
+    ClientNotification,
+    TransientError,
+    DatabaseError,
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("'{code}' with message: '{message}'")]
+/// An error reported by the server in a `FAILURE`. Its code has the form
+/// `Neo.<classification>.<category>.<title>`:
+/// ```
+/// # use raio::client::error::{Neo4jError, Classification};
+/// let error = Neo4jError::new("Neo.TransientError.Transaction.DeadlockDetected", "Deadlock");
+///
+/// assert_eq!(error.classification(), Classification::TransientError);
+/// assert_eq!(error.category(), "Transaction");
+/// assert_eq!(error.title(), "DeadlockDetected");
+/// assert!(error.is_retryable());
+/// ```
+pub struct Neo4jError {
+    pub code: String,
+    pub message: String,
+}
+
+impl Neo4jError {
+    pub fn new(code: &str, message: &str) -> Self {
+        Neo4jError {
+            code: String::from(code),
+            message: String::from(message),
+        }
+    }
+
+    fn part(&self, n: usize) -> &str {
+        self.code.split('.').nth(n).unwrap_or("")
+    }
+
+    pub fn classification(&self) -> Classification {
+        match self.part(1) {
+            "ClientError" => Classification::ClientError,
+            "ClientNotification" => Classification::ClientNotification,
+            "TransientError" => Classification::TransientError,
+            "DatabaseError" => Classification::DatabaseError,
+            _ => Classification::Unknown,
+        }
+    }
+
+    pub fn category(&self) -> &str {
+        self.part(2)
+    }
+
+    pub fn title(&self) -> &str {
+        self.part(3)
+    }
+
+    /// Checks if the same work might succeed when tried again, which is the case for transient
+    /// errors (except for terminated transactions) and for cluster role changes.
+    pub fn is_retryable(&self) -> bool {
+        match self.code.as_str() {
+            "Neo.TransientError.Transaction.Terminated" |
+            "Neo.TransientError.Transaction.LockClientStopped" => false,
+            "Neo.ClientError.Cluster.NotALeader" |
+            "Neo.ClientError.General.ForbiddenOnReadOnlyDatabase" => true,
+            _ => self.classification() == Classification::TransientError,
+        }
+    }
+
+    /// Checks if the token or the authorization of the connection expired.
+    pub fn is_auth_expired(&self) -> bool {
+        matches!(
+            self.code.as_str(),
+            "Neo.ClientError.Security.TokenExpired" | "Neo.ClientError.Security.AuthorizationExpired")
+    }
+
+    /// Checks if the server is no longer the writer of the database, such that a routing table
+    /// naming it as one is outdated.
+    pub fn is_not_a_leader(&self) -> bool {
+        matches!(
+            self.code.as_str(),
+            "Neo.ClientError.Cluster.NotALeader" | "Neo.ClientError.General.ForbiddenOnReadOnlyDatabase")
+    }
+
+    /// Checks if the connection which received this error cannot be used any further, which is
+    /// the case for database errors, malformed requests and expired authorizations.
+    pub fn is_fatal_to_connection(&self) -> bool {
+        match self.code.as_str() {
+            "Neo.ClientError.Request.Invalid" |
+            "Neo.ClientError.Request.InvalidFormat" |
+            "Neo.ClientError.Security.AuthorizationExpired" |
+            "Neo.ClientError.Security.TokenExpired" => true,
+            _ => self.classification() == Classification::DatabaseError,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+/// A failed authentication, classified by the code of the server's `FAILURE`:
+/// ```
+/// # use raio::client::error::{AuthError, Neo4jError};
+/// let error = AuthError::from(Neo4jError::new("Neo.ClientError.Security.TokenExpired", "Expired"));
+///
+/// assert!(matches!(error, AuthError::TokenExpired(_)));
+/// assert!(error.needs_reauthentication());
+/// assert!(!error.is_retryable());
+/// ```
+pub enum AuthError {
+    /// Unknown user or wrong password (`Neo.ClientError.Security.Unauthorized`).
+    #[error("Invalid credentials: {0}")]
+    InvalidCredentials(Neo4jError),
+    /// The token or the authorization expired; the same work succeeds with a fresh token.
+    #[error("Token expired: {0}")]
+    TokenExpired(Neo4jError),
+    /// The credentials are known but not allowed in, e.g. since the password has to be changed.
+    #[error("Unauthorized: {0}")]
+    Unauthorized(Neo4jError),
+    /// Too many failed attempts; authenticating again might succeed later.
+    #[error("Authentication rate limited: {0}")]
+    RateLimited(Neo4jError),
+    #[error("Authentication failed: {0}")]
+    Other(Neo4jError),
+}
+
+impl AuthError {
+    /// The failure reported by the server.
+    pub fn neo4j_error(&self) -> &Neo4jError {
+        match self {
+            AuthError::InvalidCredentials(e)
+            | AuthError::TokenExpired(e)
+            | AuthError::Unauthorized(e)
+            | AuthError::RateLimited(e)
+            | AuthError::Other(e) => e,
+        }
+    }
+
+    /// Checks if authenticating with a fresh token from the auth provider might succeed.
+    pub fn needs_reauthentication(&self) -> bool {
+        matches!(self, AuthError::TokenExpired(_))
+    }
+
+    /// Checks if authenticating with the same credentials might succeed when tried again later.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, AuthError::RateLimited(_))
+    }
+}
+
+impl From<Neo4jError> for AuthError {
+    fn from(error: Neo4jError) -> Self {
+        match error.code.as_str() {
+            "Neo.ClientError.Security.Unauthorized" =>
+                AuthError::InvalidCredentials(error),
+            "Neo.ClientError.Security.TokenExpired" |
+            "Neo.ClientError.Security.AuthorizationExpired" =>
+                AuthError::TokenExpired(error),
+            "Neo.ClientError.Security.Forbidden" |
+            "Neo.ClientError.Security.CredentialsExpired" =>
+                AuthError::Unauthorized(error),
+            "Neo.ClientError.Security.AuthenticationRateLimit" =>
+                AuthError::RateLimited(error),
+            _ => AuthError::Other(error),
+        }
+    }
+}
+
+impl From<Failure> for Neo4jError {
+    fn from(mut f: Failure) -> Self {
+        Neo4jError {
+            code: f.code(),
+            message: f.message(),
+        }
+    }
 }
 
 impl From<PoolError<ConnectionError>> for ClientError {
     fn from(e: PoolError<ConnectionError>) -> Self {
         match e {
+            PoolError::Backend(ConnectionError::ServerUnavailable) => ClientError::ServerUnavailable,
             PoolError::Backend(err) => ClientError::ConnectionError(err),
             PoolError::Timeout(_) => ClientError::PoolTimeOut,
         }
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use packs::Value;
+use packs::std_structs::{StdStruct, Node, Relationship, Path};
+
+use crate::client::path::PathExt;
+use crate::client::record_result::RecordResult;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+/// The distinct nodes and relationships of a result, keyed by their id. Nodes and relationships
+/// are collected from every field of every record, including lists, maps and paths:
+/// ```
+/// # use raio::client::graph::Graph;
+/// # use packs::{Value, Dictionary};
+/// # use packs::std_structs::{StdStruct, Node, Relationship};
+/// let mut graph = Graph::new();
+/// graph.add_value(&Value::from(Node::new(1)));
+/// graph.add_value(&Value::from(vec![Value::from(Node::new(2)), Value::from(Node::new(1))]));
+/// graph.add_value(&Value::from(
+///     Relationship {
+///         id: 7,
+///         start_node_id: 1,
+///         end_node_id: 2,
+///         _type: String::from("KNOWS"),
+///         properties: Dictionary::new(),
+///     }));
+///
+/// assert_eq!(graph.nodes().count(), 2);
+/// assert_eq!(graph.outgoing(1).count(), 1);
+/// assert_eq!(graph.neighbours(2).map(|n| n.id).collect::<Vec<_>>(), vec![1]);
+/// ```
+pub struct Graph {
+    nodes: HashMap<i64, Node>,
+    relationships: HashMap<i64, Relationship>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Graph {
+            nodes: HashMap::new(),
+            relationships: HashMap::new(),
+        }
+    }
+
+    /// Collects the graph of all provided records.
+    pub fn from_records(records: &[RecordResult]) -> Self {
+        let mut graph = Graph::new();
+        for record in records.iter() {
+            for value in record.values() {
+                graph.add_value(value);
+            }
+        }
+
+        graph
+    }
+
+    /// Adds every node and relationship contained in `value`.
+    #[allow(unreachable_patterns)]
+    pub fn add_value(&mut self, value: &Value<StdStruct>) {
+        match value {
+            Value::List(list) =>
+                list.iter().for_each(|v| self.add_value(v)),
+            Value::Dictionary(dict) =>
+                dict.iter().for_each(|(_, v)| self.add_value(v)),
+            Value::Structure(StdStruct::Node(node)) =>
+                self.add_node(node),
+            Value::Structure(StdStruct::Relationship(rel)) =>
+                self.add_relationship(rel),
+            Value::Structure(StdStruct::Path(path)) =>
+                self.add_path(path),
+            _ => {}
+        }
+    }
+
+    pub fn add_node(&mut self, node: &Node) {
+        self.nodes.entry(node.id).or_insert_with(|| node.clone());
+    }
+
+    pub fn add_relationship(&mut self, rel: &Relationship) {
+        self.relationships.entry(rel.id).or_insert_with(|| rel.clone());
+    }
+
+    /// Adds the nodes and relationships of a path. The relationships of a path are unbound, their
+    /// start and end nodes are taken from the path sequence.
+    pub fn add_path(&mut self, path: &Path) {
+        path.nodes.iter().for_each(|n| self.add_node(n));
+        path.iter_segments().for_each(|s| self.add_relationship(&s.bound_relationship()));
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.values()
+    }
+
+    pub fn relationships(&self) -> impl Iterator<Item = &Relationship> {
+        self.relationships.values()
+    }
+
+    pub fn node(&self, id: i64) -> Option<&Node> {
+        self.nodes.get(&id)
+    }
+
+    pub fn relationship(&self, id: i64) -> Option<&Relationship> {
+        self.relationships.get(&id)
+    }
+
+    /// All relationships starting at the node with the provided id.
+    pub fn outgoing(&self, id: i64) -> impl Iterator<Item = &Relationship> {
+        self.relationships().filter(move |r| r.start_node_id == id)
+    }
+
+    /// All relationships ending at the node with the provided id.
+    pub fn incoming(&self, id: i64) -> impl Iterator<Item = &Relationship> {
+        self.relationships().filter(move |r| r.end_node_id == id)
+    }
+
+    /// All nodes connected to the node with the provided id by a relationship of any direction,
+    /// once per relationship.
+    pub fn neighbours(&self, id: i64) -> impl Iterator<Item = &Node> {
+        self.relationships()
+            .filter_map(move |r| {
+                if r.start_node_id == id {
+                    Some(r.end_node_id)
+                } else if r.end_node_id == id {
+                    Some(r.start_node_id)
+                } else {
+                    None
+                }
+            })
+            .filter_map(move |n| self.nodes.get(&n))
+    }
+}
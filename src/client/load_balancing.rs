@@ -0,0 +1,78 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// A cluster member the routing client might send a request to.
+pub struct Candidate<'a> {
+    pub address: &'a str,
+    /// The number of connections of the member which are in use or waited for.
+    pub in_flight: usize,
+}
+
+/// Picks the member of a cluster which a request of the
+/// [`RoutingClient`](crate::client::routing::RoutingClient) is sent to, out of the readers resp.
+/// the writers of the routing table:
+/// ```
+/// # use raio::client::load_balancing::{Candidate, LeastInFlight, LoadBalancer, RoundRobin};
+/// let candidates = [
+///     Candidate { address: "a:7687", in_flight: 3 },
+///     Candidate { address: "b:7687", in_flight: 1 },
+/// ];
+///
+/// let round_robin = RoundRobin::default();
+/// assert_eq!(round_robin.select(&candidates), 0);
+/// assert_eq!(round_robin.select(&candidates), 1);
+/// assert_eq!(LeastInFlight::default().select(&candidates), 1);
+/// ```
+pub trait LoadBalancer: Send + Sync {
+    /// The index of the chosen one of `candidates`, which is never empty.
+    fn select(&self, candidates: &[Candidate]) -> usize;
+}
+
+impl fmt::Debug for dyn LoadBalancer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LoadBalancer")
+    }
+}
+
+#[derive(Debug, Default)]
+/// Picks the candidates in turn; the default.
+pub struct RoundRobin {
+    next: AtomicUsize,
+}
+
+impl LoadBalancer for RoundRobin {
+    fn select(&self, candidates: &[Candidate]) -> usize {
+        self.next.fetch_add(1, Ordering::Relaxed) % candidates.len()
+    }
+}
+
+#[derive(Debug, Default)]
+/// Picks the candidate with the fewest connections in use, in turn among equally busy ones.
+pub struct LeastInFlight {
+    next: AtomicUsize,
+}
+
+impl LoadBalancer for LeastInFlight {
+    fn select(&self, candidates: &[Candidate]) -> usize {
+        let offset = self.next.fetch_add(1, Ordering::Relaxed);
+        (0..candidates.len())
+            .map(|i| (i + offset) % candidates.len())
+            .min_by_key(|&i| candidates[i].in_flight)
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Default)]
+/// Picks a candidate at random.
+pub struct Random;
+
+impl LoadBalancer for Random {
+    fn select(&self, candidates: &[Candidate]) -> usize {
+        // every `RandomState` is seeded randomly:
+        let random = RandomState::new().build_hasher().finish();
+        (random % candidates.len() as u64) as usize
+    }
+}
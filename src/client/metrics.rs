@@ -0,0 +1,82 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::client::error::ClientError;
+use crate::client::query_log::QueryLog;
+use crate::messaging::query::Query;
+
+/// Receives the events of a client, e.g. to export metrics. Every method does nothing by default,
+/// so an observer only implements the events it is interested in:
+/// ```
+/// # use std::sync::Arc;
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// # use std::time::Duration;
+/// # use raio::client::ClientConfig;
+/// # use raio::client::metrics::MetricsObserver;
+/// # use raio::messaging::query::Query;
+/// #[derive(Default)]
+/// struct RecordCounter(AtomicUsize);
+///
+/// impl MetricsObserver for RecordCounter {
+///     fn query_finished(&self, _query: &Query, _duration: Duration, records: usize) {
+///         self.0.fetch_add(records, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let config =
+///     ClientConfig::default("my-app", "0.1.0")
+///         .metrics_observer(Arc::new(RecordCounter::default()));
+/// ```
+pub trait MetricsObserver: Send + Sync {
+    /// Called before a query is sent.
+    fn query_started(&self, _query: &Query) {}
+
+    /// Called after all records of a query were received.
+    fn query_finished(&self, _query: &Query, _duration: Duration, _records: usize) {}
+
+    /// Called after a new connection was established and authenticated.
+    fn connection_created(&self) {}
+
+    /// Called if a query failed; for failures reported by the server, the code is given by
+    /// [`neo4j_error`](crate::client::error::ClientError::neo4j_error).
+    fn failure(&self, _query: &Query, _error: &ClientError) {}
+}
+
+impl fmt::Debug for dyn MetricsObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MetricsObserver")
+    }
+}
+
+#[derive(Debug, Clone)]
+/// The observer and query log of a client, which are notified about each of its queries.
+pub(crate) struct Instruments {
+    pub(crate) observer: Option<Arc<dyn MetricsObserver>>,
+    pub(crate) query_log: Option<QueryLog>,
+}
+
+impl Instruments {
+    pub(crate) fn query_started(&self, query: &Query) -> Instant {
+        if let Some(observer) = &self.observer {
+            observer.query_started(query);
+        }
+
+        Instant::now()
+    }
+
+    /// Reports the number of records or the error of a query started at `started`.
+    pub(crate) fn query_finished(&self, query: &Query, started: Instant, outcome: Result<usize, &ClientError>) {
+        let duration = started.elapsed();
+        if let Some(observer) = &self.observer {
+            match outcome {
+                Ok(records) => observer.query_finished(query, duration, records),
+                Err(e) => observer.failure(query, e),
+            }
+        }
+
+        if let Some(log) = &self.query_log {
+            log.log(query, duration, outcome);
+        }
+    }
+}
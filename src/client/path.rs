@@ -0,0 +1,145 @@
+use std::convert::TryFrom;
+
+use packs::std_structs::{Node, Path, Relationship, UnboundRelationship};
+
+use crate::client::relationship::UnboundRelationshipExt;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// The direction in which a relationship of a path is traversed.
+pub enum Direction {
+    /// From the start node of the relationship to its end node.
+    Forward,
+    Backward,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// A single step of a path, from `start` over `relationship` to `end`.
+pub struct Segment<'p> {
+    pub start: &'p Node,
+    pub relationship: &'p UnboundRelationship,
+    pub end: &'p Node,
+    pub direction: Direction,
+}
+
+impl<'p> Segment<'p> {
+    /// The relationship bound to its start and end node, which are `start` and `end` if it is
+    /// traversed forwards and swapped otherwise.
+    pub fn bound_relationship(&self) -> Relationship {
+        let (start, end) =
+            match self.direction {
+                Direction::Forward => (self.start, self.end),
+                Direction::Backward => (self.end, self.start),
+            };
+
+        self.relationship.bind(start.id, end.id)
+    }
+}
+
+/// Walks a path in order. The sequence of a path alternates relationship indices, which are
+/// 1-based and negative if the relationship is traversed backwards, and node indices:
+/// ```
+/// # use raio::client::path::{Direction, PathExt};
+/// # use packs::Dictionary;
+/// # use packs::std_structs::{Node, Path, UnboundRelationship};
+/// let knows = UnboundRelationship { id: 10, _type: String::from("KNOWS"), properties: Dictionary::new() };
+/// // (1)-[:KNOWS]->(2)<-[:KNOWS]-(1):
+/// let path = Path {
+///     nodes: vec![Node::new(1), Node::new(2)],
+///     rels: vec![knows],
+///     ids: vec![1, 1, -1, 0],
+/// };
+///
+/// let walk: Vec<(i64, i64, Direction)> =
+///     path.iter_segments().map(|s| (s.start.id, s.end.id, s.direction)).collect();
+/// assert_eq!(walk, vec![(1, 2, Direction::Forward), (2, 1, Direction::Backward)]);
+/// assert_eq!(path.len(), 2);
+/// assert!(path.contains_node(2));
+/// ```
+pub trait PathExt {
+    fn iter_segments(&self) -> Segments<'_>;
+
+    /// The number of relationships traversed.
+    fn len(&self) -> usize;
+
+    /// Checks if the path consists of its start node only.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn contains_node(&self, id: i64) -> bool;
+
+    fn start_node(&self) -> Option<&Node>;
+
+    fn end_node(&self) -> Option<&Node>;
+}
+
+impl PathExt for Path {
+    fn iter_segments(&self) -> Segments<'_> {
+        Segments {
+            path: self,
+            previous: self.nodes.first(),
+            step: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.ids.len() / 2
+    }
+
+    fn contains_node(&self, id: i64) -> bool {
+        self.nodes.iter().any(|n| n.id == id)
+    }
+
+    fn start_node(&self) -> Option<&Node> {
+        self.nodes.first()
+    }
+
+    fn end_node(&self) -> Option<&Node> {
+        match self.iter_segments().last() {
+            Some(segment) => Some(segment.end),
+            None => self.start_node(),
+        }
+    }
+}
+
+/// The segments of a path, see [`PathExt::iter_segments`](crate::client::path::PathExt::iter_segments).
+/// Ends early if the sequence of the path is malformed.
+pub struct Segments<'p> {
+    path: &'p Path,
+    previous: Option<&'p Node>,
+    step: usize,
+}
+
+impl<'p> Iterator for Segments<'p> {
+    type Item = Segment<'p>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.previous?;
+        let (rel_index, node_index) =
+            match self.path.ids.get(2 * self.step..2 * self.step + 2)? {
+                [r, n] => (*r, *n),
+                _ => return None,
+            };
+
+        let relationship =
+            (rel_index.unsigned_abs() as usize).checked_sub(1).and_then(|i| self.path.rels.get(i));
+        let end = usize::try_from(node_index).ok().and_then(|i| self.path.nodes.get(i));
+        let (relationship, end) =
+            match (relationship, end) {
+                (Some(relationship), Some(end)) => (relationship, end),
+                _ => {
+                    self.previous = None;
+                    return None;
+                }
+            };
+
+        self.step += 1;
+        self.previous = Some(end);
+        Some(Segment {
+            start,
+            relationship,
+            end,
+            direction: if rel_index > 0 { Direction::Forward } else { Direction::Backward },
+        })
+    }
+}
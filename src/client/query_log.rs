@@ -0,0 +1,88 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use packs::Dictionary;
+use packs::std_structs::StdStruct;
+
+use crate::client::error::ClientError;
+use crate::messaging::query::Query;
+
+/// A query as it is reported to a [`QueryLog`](crate::client::query_log::QueryLog) after it
+/// finished.
+pub struct QueryLogEntry<'a> {
+    pub statement: &'a str,
+    /// The parameters of the query; `None` if the log redacts them.
+    pub parameters: Option<&'a Dictionary<StdStruct>>,
+    pub duration: Duration,
+    /// The number of records or the error the query failed with.
+    pub outcome: Result<usize, &'a ClientError>,
+}
+
+#[derive(Clone)]
+/// A callback which is called after each query, or only after slow ones:
+/// ```
+/// # use std::time::Duration;
+/// # use raio::client::ClientConfig;
+/// # use raio::client::query_log::QueryLog;
+/// let log =
+///     QueryLog::new(|entry| eprintln!("{} took {:?}", entry.statement, entry.duration))
+///         .slow_query_threshold(Duration::from_millis(500))
+///         .redact_parameters(true);
+///
+/// let config = ClientConfig::default("my-app", "0.1.0").query_log(log);
+/// ```
+pub struct QueryLog {
+    callback: Arc<dyn Fn(&QueryLogEntry) + Send + Sync>,
+    slow_query_threshold: Option<Duration>,
+    redact_parameters: bool,
+}
+
+impl QueryLog {
+    /// Creates a log which reports every query, including its parameters, to `callback`.
+    pub fn new<F>(callback: F) -> Self
+        where F: Fn(&QueryLogEntry) + Send + Sync + 'static {
+        QueryLog {
+            callback: Arc::new(callback),
+            slow_query_threshold: None,
+            redact_parameters: false,
+        }
+    }
+
+    /// Only reports queries which took at least `threshold`.
+    pub fn slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_query_threshold = Some(threshold);
+        self
+    }
+
+    /// Does not report the parameters of queries, e.g. since they contain personal data.
+    pub fn redact_parameters(mut self, redact: bool) -> Self {
+        self.redact_parameters = redact;
+        self
+    }
+
+    pub(crate) fn log(&self, query: &Query, duration: Duration, outcome: Result<usize, &ClientError>) {
+        if matches!(self.slow_query_threshold, Some(threshold) if duration < threshold) {
+            return;
+        }
+
+        let parameters =
+            if self.redact_parameters { None } else { Some(query.parameters()) };
+
+        (self.callback)(&QueryLogEntry {
+            statement: query.statement(),
+            parameters,
+            duration,
+            outcome,
+        });
+    }
+}
+
+impl fmt::Debug for QueryLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryLog")
+            .field("slow_query_threshold", &self.slow_query_threshold)
+            .field("redact_parameters", &self.redact_parameters)
+            .finish()
+    }
+}
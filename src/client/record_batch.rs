@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use ::arrow::array::{Array, ArrayRef, BooleanArray, Float64Array, Int64Array, NullArray, StringArray};
+use ::arrow::datatypes::{DataType, Field, Schema};
+use ::arrow::error::ArrowError;
+use ::arrow::record_batch::RecordBatch;
+use packs::Value;
+use packs::std_structs::StdStruct;
+use thiserror::Error;
+
+use crate::client::convert::value_kind;
+
+#[derive(Debug, Error)]
+pub enum RecordBatchError {
+    #[error("Column '{field}' contains {kind} values, which have no arrow representation")]
+    UnsupportedType { field: String, kind: &'static str },
+    #[error("Column '{field}' mixes {first} and {second} values")]
+    MixedTypes { field: String, first: &'static str, second: &'static str },
+    #[error("Arrow error: {0}")]
+    ArrowError(#[from] ArrowError),
+}
+
+/// Builds a `RecordBatch` with one array per column. The type of a column is taken from its
+/// values: integers, floats, booleans and strings map to `Int64`, `Float64`, `Boolean` and
+/// `Utf8`, a column of integers and floats to `Float64` and a column of `null`s only to `Null`.
+/// `null`s are allowed in every column.
+pub fn record_batch<'a, C>(columns: C) -> Result<RecordBatch, RecordBatchError>
+    where C: IntoIterator<Item = (&'a str, &'a [Value<StdStruct>])> {
+    let mut fields = Vec::new();
+    let mut arrays = Vec::new();
+    for (field, values) in columns {
+        let array = array(field, values)?;
+        fields.push(Field::new(field, array.data_type().clone(), true));
+        arrays.push(array);
+    }
+
+    Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+}
+
+fn array(field: &str, values: &[Value<StdStruct>]) -> Result<ArrayRef, RecordBatchError> {
+    let array: ArrayRef =
+        match column_type(field, values)? {
+            DataType::Int64 =>
+                Arc::new(Int64Array::from(values.iter().map(|v| match v {
+                    Value::Integer(i) => Some(*i),
+                    _ => None,
+                }).collect::<Vec<_>>())),
+            DataType::Float64 =>
+                Arc::new(Float64Array::from(values.iter().map(|v| match v {
+                    Value::Float(f) => Some(*f),
+                    Value::Integer(i) => Some(*i as f64),
+                    _ => None,
+                }).collect::<Vec<_>>())),
+            DataType::Boolean =>
+                Arc::new(BooleanArray::from(values.iter().map(|v| match v {
+                    Value::Boolean(b) => Some(*b),
+                    _ => None,
+                }).collect::<Vec<_>>())),
+            DataType::Utf8 =>
+                Arc::new(StringArray::from(values.iter().map(|v| match v {
+                    Value::String(s) => Some(s.as_str()),
+                    _ => None,
+                }).collect::<Vec<_>>())),
+            _ =>
+                Arc::new(NullArray::new(values.len())),
+        };
+
+    Ok(array)
+}
+
+fn column_type(field: &str, values: &[Value<StdStruct>]) -> Result<DataType, RecordBatchError> {
+    let mut column_type = DataType::Null;
+    let mut first = "Null";
+    for value in values {
+        let value_type =
+            match value {
+                Value::Null => continue,
+                Value::Integer(_) => DataType::Int64,
+                Value::Float(_) => DataType::Float64,
+                Value::Boolean(_) => DataType::Boolean,
+                Value::String(_) => DataType::Utf8,
+                v => return Err(RecordBatchError::UnsupportedType {
+                    field: String::from(field),
+                    kind: value_kind(v),
+                }),
+            };
+
+        column_type =
+            match (&column_type, value_type) {
+                (DataType::Null, t) => {
+                    first = value_kind(value);
+                    t
+                }
+                (t, u) if *t == u => u,
+                (DataType::Int64, DataType::Float64) | (DataType::Float64, DataType::Int64) =>
+                    DataType::Float64,
+                _ => return Err(RecordBatchError::MixedTypes {
+                    field: String::from(field),
+                    first,
+                    second: value_kind(value),
+                }),
+            };
+    }
+
+    Ok(column_type)
+}
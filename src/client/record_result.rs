@@ -1,46 +1,312 @@
-use packs::{Dictionary, ExtractRef, Value};
+use std::sync::Arc;
+
+use packs::{Dictionary, Extract, ExtractRef, Value};
 use packs::std_structs::StdStruct;
+use thiserror::Error;
 use crate::messaging::response::{Record};
 use crate::client::error::ClientError;
+use crate::client::convert::{FromValue, ValueError, value_kind};
+
+#[cfg(feature = "derive")]
+pub use raio_derive::FromRecord;
+#[cfg(feature = "serde")]
+use crate::messaging::serialization::{SerdeError, SerializeValue, from_dictionary};
+#[cfg(feature = "serde")]
+use serde::ser::SerializeMap;
 
 #[derive(Debug, Clone)]
-/// A structure which captures a `RECORD` response into a result row.
+/// A structure which captures a `RECORD` response into a result row. The field names are shared
+/// by all rows of a result, hence a row only holds its values:
+/// ```
+/// # use raio::client::record_result::RecordResult;
+/// # use raio::messaging::response::Record;
+/// # use packs::Value;
+/// let fields = vec![String::from("name"), String::from("age")];
+/// let records = vec![
+///     Record { data: vec![Value::from("Alice"), Value::Integer(42)] },
+///     Record { data: vec![Value::from("Bob"), Value::Integer(23)] },
+/// ];
+/// let rows = RecordResult::from_results(&fields, records).unwrap();
+///
+/// assert!(std::ptr::eq(rows[0].fields(), rows[1].fields()));
+/// assert_eq!(rows[1].get_field_typed::<i64>("age"), Some(&23));
+/// assert_eq!(rows[1].value(0), Some(&Value::from("Bob")));
+/// ```
 pub struct RecordResult {
-    pub data: Dictionary<StdStruct>,
+    fields: Arc<[String]>,
+    values: Vec<Value<StdStruct>>,
 }
 
 impl RecordResult {
     /// Uses the `fields` information to augment a `RECORD` with field names.
     pub fn new(success_fields: &[String], record: Record) -> Result<Self, ClientError> {
-        if success_fields.len() != record.data.len() {
-            return Err(ClientError::FieldsToRecordMismatch)
-        }
+        RecordResult::with_fields(Arc::from(success_fields), record)
+    }
 
-        let mut data = Dictionary::with_capacity(success_fields.len());
-        for (f, v) in success_fields.into_iter().zip(record.data.into_iter()) {
-            data.add_property(f, v);
+    /// Like [`new`](crate::client::record_result::RecordResult::new), but shares the field names
+    /// with other rows.
+    pub fn with_fields(fields: Arc<[String]>, record: Record) -> Result<Self, ClientError> {
+        if fields.len() != record.data.len() {
+            return Err(ClientError::FieldsToRecordMismatch)
         }
 
         Ok(RecordResult {
-            data,
+            fields,
+            values: record.data,
         })
     }
-    
+
     pub fn from_results(fields: &[String], records: Vec<Record>) -> Result<Vec<Self>, ClientError> {
+        let fields: Arc<[String]> = Arc::from(fields);
         let mut results = Vec::with_capacity(records.len());
         for r in records.into_iter() {
-            results.push(RecordResult::new(fields, r)?);
+            results.push(RecordResult::with_fields(fields.clone(), r)?);
         }
         Ok(results)
     }
-    
+
+    /// The position of the field `key` in the row.
+    pub fn index_of(&self, key: &str) -> Option<usize> {
+        self.fields.iter().position(|f| f == key)
+    }
+
+    /// The value of the column at `index`.
+    pub fn value(&self, index: usize) -> Option<&Value<StdStruct>> {
+        self.values.get(index)
+    }
+
+    /// The values of the row, in the order of the result columns.
+    pub fn values(&self) -> &[Value<StdStruct>] {
+        &self.values
+    }
+
+    pub fn into_values(self) -> Vec<Value<StdStruct>> {
+        self.values
+    }
+
+    /// The pairs of field name and value, in the order of the result columns.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value<StdStruct>)> {
+        self.fields.iter().map(String::as_str).zip(self.values.iter())
+    }
+
+    /// The row as a dictionary keyed by the field names.
+    pub fn into_dictionary(self) -> Dictionary<StdStruct> {
+        let mut data = Dictionary::with_capacity(self.fields.len());
+        for (f, v) in self.fields.iter().zip(self.values) {
+            data.add_property(f, v);
+        }
+
+        data
+    }
+
     pub fn get_field_typed<T: ExtractRef<StdStruct>>(&self, key: &str) -> Option<&T> {
-        self.data.get_property_typed(key)
+        self.get_field(key).and_then(T::extract_ref)
     }
 
     pub fn get_field(&self, key: &str) -> Option<&Value<StdStruct>> {
-        self.data.get_property(key)
+        self.index_of(key).and_then(|i| self.values.get(i))
+    }
+
+    /// Takes the value of the field `key` out of the row, if it is of type `T`; the field is
+    /// `null` afterwards.
+    pub fn extract_field_typed<T: Extract<StdStruct>>(&mut self, key: &str) -> Option<T> {
+        let index = self.index_of(key)?;
+        T::extract(std::mem::replace(&mut self.values[index], Value::Null))
+    }
+
+    /// Takes the value of the field `key` out of the row like
+    /// [`extract_field_typed`](crate::client::record_result::RecordResult::extract_field_typed),
+    /// but distinguishes a missing field and a value of another type, which are errors, from a
+    /// `null` value, which is `None`:
+    /// ```
+    /// # use raio::client::record_result::{RecordResult, ConversionError};
+    /// # use raio::messaging::response::Record;
+    /// # use packs::Value;
+    /// let fields = vec![String::from("name"), String::from("email")];
+    /// let record = Record { data: vec![Value::from("Jane"), Value::Null] };
+    /// let mut row = RecordResult::new(&fields, record).unwrap();
+    ///
+    /// assert!(matches!(row.try_extract::<i64>("name"), Err(ConversionError::InvalidValue { .. })));
+    /// assert_eq!(row.try_extract::<String>("email"), Ok(None));
+    /// assert_eq!(row.try_extract::<String>("age"), Err(ConversionError::MissingField(String::from("age"))));
+    /// ```
+    pub fn try_extract<T: Extract<StdStruct>>(&mut self, key: &str) -> Result<Option<T>, ConversionError> {
+        let index = self.index_of(key)
+            .ok_or_else(|| ConversionError::MissingField(String::from(key)))?;
+
+        let value = std::mem::replace(&mut self.values[index], Value::Null);
+        if let Value::Null = value {
+            return Ok(None)
+        }
+
+        let actual = value_kind(&value);
+        T::extract(value)
+            .map(Some)
+            .ok_or_else(|| ConversionError::InvalidValue {
+                field: String::from(key),
+                error: ValueError::UnexpectedType { expected: std::any::type_name::<T>(), actual },
+            })
+    }
+
+    /// Gets the field `key` as `T`, distinguishing a missing field and a value of another type,
+    /// which are errors, from a `null` value, which is `None`:
+    /// ```
+    /// # use raio::client::record_result::{RecordResult, ConversionError};
+    /// # use raio::messaging::response::Record;
+    /// # use packs::Value;
+    /// let fields = vec![String::from("name"), String::from("email")];
+    /// let record = Record { data: vec![Value::from("Jane"), Value::Null] };
+    /// let row = RecordResult::new(&fields, record).unwrap();
+    ///
+    /// assert_eq!(row.try_get::<String>("name"), Ok(Some(&String::from("Jane"))));
+    /// assert_eq!(row.try_get::<String>("email"), Ok(None));
+    /// assert_eq!(row.try_get::<String>("age"), Err(ConversionError::MissingField(String::from("age"))));
+    /// assert!(matches!(row.try_get::<i64>("name"), Err(ConversionError::UnexpectedType { actual: "String", .. })));
+    /// ```
+    pub fn try_get<T: ExtractRef<StdStruct>>(&self, key: &str) -> Result<Option<&T>, ConversionError> {
+        let value = self.get_field(key)
+            .ok_or_else(|| ConversionError::MissingField(String::from(key)))?;
+
+        if let Value::Null = value {
+            return Ok(None)
+        }
+
+        T::extract_ref(value)
+            .map(Some)
+            .ok_or_else(|| ConversionError::UnexpectedType {
+                field: String::from(key),
+                expected: std::any::type_name::<T>(),
+                actual: value_kind(value),
+            })
+    }
+
+    /// Converts the field `key` into `T`, checking that the value fits:
+    /// ```
+    /// # use raio::client::record_result::RecordResult;
+    /// # use raio::messaging::response::Record;
+    /// # use packs::Value;
+    /// let fields = vec![String::from("age"), String::from("score")];
+    /// let record = Record { data: vec![Value::Integer(42), Value::Float(0.5)] };
+    /// let row = RecordResult::new(&fields, record).unwrap();
+    ///
+    /// assert_eq!(row.get_as::<u8>("age"), Ok(42));
+    /// assert_eq!(row.get_as::<f32>("score"), Ok(0.5));
+    /// assert!(row.get_as::<u8>("score").is_err());
+    /// ```
+    pub fn get_as<T: FromValue>(&self, key: &str) -> Result<T, ConversionError> {
+        let value = self.get_field(key)
+            .ok_or_else(|| ConversionError::MissingField(String::from(key)))?;
+
+        T::from_value(value)
+            .map_err(|error| ConversionError::InvalidValue { field: String::from(key), error })
+    }
+
+    /// The field names of the row, in the order of the result columns.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// Gets the value of the column at `index`, if it is of type `T`.
+    pub fn get<T: ExtractRef<StdStruct>>(&self, index: usize) -> Option<&T> {
+        self.values.get(index).and_then(T::extract_ref)
+    }
+
+    /// Converts the row into a tuple with one element per column, in the order of the result
+    /// columns, e.g. `(String, i64)` for `RETURN n.name, n.age`:
+    /// ```no_run
+    /// # use raio::client::{Client, ClientConfig};
+    /// # use raio::client::auth::Basic;
+    /// # use raio::messaging::query::Query;
+    /// # #[async_std::main]
+    /// # async fn main() -> Result<(), raio::client::error::ClientError> {
+    /// # let client = Client::create("localhost:7687", Basic::new("neo4j", "test"), ClientConfig::default("test", "0.1"));
+    /// let result = client.query(&Query::new("RETURN 'Alice' as name, 42 as age")).await?;
+    /// let (name, age) = result.into_records().remove(0).into_tuple::<(String, i64)>()?;
+    ///
+    /// assert_eq!(name, "Alice");
+    /// assert_eq!(age, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_tuple<T: FromRecord>(self) -> Result<T, ClientError> {
+        T::from_record(self)
+    }
+
+    /// Extracts the value of the column at `index`, if it is of type `T`.
+    fn take<T: Extract<StdStruct>>(&mut self, index: usize) -> Result<T, ClientError> {
+        let value = match self.values.get_mut(index) {
+            Some(value) => std::mem::replace(value, Value::Null),
+            None => return Err(ClientError::FieldsToRecordMismatch),
+        };
+
+        T::extract(value)
+            .ok_or_else(|| ClientError::MissingRecordField(self.fields[index].clone()))
+    }
+
+    /// Deserializes the whole row into `T`, using the field names as keys.
+    #[cfg(feature = "serde")]
+    pub fn deserialize_into<T: serde::de::DeserializeOwned>(self) -> Result<T, SerdeError> {
+        from_dictionary(self.into_dictionary())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for RecordResult {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.values.len()))?;
+        for (field, value) in self.iter() {
+            map.serialize_entry(field, &SerializeValue(value))?;
+        }
+        map.end()
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ConversionError {
+    #[error("Record has no field '{0}'")]
+    MissingField(String),
+    #[error("Field '{field}' is of kind {actual}, but {expected} was expected")]
+    UnexpectedType { field: String, expected: &'static str, actual: &'static str },
+    #[error("Field '{field}' cannot be converted: {error}")]
+    InvalidValue { field: String, error: ValueError },
+}
+
+/// Types which can be built out of a single result row. With the `derive` feature, it can be
+/// derived for structs, mapping every field by its name:
+/// ```ignore
+/// use raio::client::record_result::FromRecord;
+///
+/// #[derive(FromRecord)]
+/// struct Person {
+///     name: String,
+///     age: i64,
+///     email: Option<String>,
+/// }
+/// ```
+pub trait FromRecord: Sized {
+    fn from_record(record: RecordResult) -> Result<Self, ClientError>;
+}
+
+macro_rules! tuple_from_record {
+    ($len:expr; $($t:ident $i:tt),+) => {
+        /// Extracts the columns of a row by position.
+        impl<$($t: Extract<StdStruct>),+> FromRecord for ($($t,)+) {
+            fn from_record(mut record: RecordResult) -> Result<Self, ClientError> {
+                if record.fields.len() != $len {
+                    return Err(ClientError::FieldsToRecordMismatch)
+                }
+
+                Ok(($(record.take::<$t>($i)?,)+))
+            }
+        }
+    };
+}
 
+tuple_from_record!(1; A 0);
+tuple_from_record!(2; A 0, B 1);
+tuple_from_record!(3; A 0, B 1, C 2);
+tuple_from_record!(4; A 0, B 1, C 2, D 3);
+tuple_from_record!(5; A 0, B 1, C 2, D 3, E 4);
+tuple_from_record!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+tuple_from_record!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple_from_record!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
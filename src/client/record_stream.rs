@@ -0,0 +1,151 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+
+use crate::client::error::ClientError;
+use crate::client::record_result::RecordResult;
+use crate::connectivity::connection::ConnectionError;
+use crate::connectivity::pool::PooledConnection;
+use crate::connectivity::stream_result::StreamResult;
+use crate::messaging::bookmark::Bookmark;
+use crate::messaging::request::{Amount, Qid};
+use crate::messaging::response::Record;
+
+type PendingPull =
+    Pin<Box<dyn Future<Output = (Cursor, Result<StreamResult, ConnectionError>)> + Send>>;
+
+/// The server side of a stream; owns the connection while the stream is open.
+struct Cursor {
+    connection: PooledConnection,
+    qid: Qid,
+    batch: Amount,
+}
+
+/// A stream of records which pulls lazily in batches from the server, i.e. sends a `PULL` with
+/// the batch size whenever all records of the former batch were consumed and the server reported
+/// more records. Only one batch is held in memory at a time.
+///
+/// The stream holds its connection until it is finished.
+pub struct RecordStream {
+    fields: Arc<[String]>,
+    buffer: VecDeque<Record>,
+    cursor: Option<Cursor>,
+    pending: Option<PendingPull>,
+    bookmark: Option<Bookmark>,
+}
+
+impl RecordStream {
+    pub(crate) fn new(
+        connection: PooledConnection,
+        fields: Vec<String>,
+        qid: Qid,
+        batch: Amount) -> Self {
+        RecordStream {
+            fields: fields.into(),
+            buffer: VecDeque::new(),
+            cursor: Some(Cursor {
+                connection,
+                qid,
+                batch,
+            }),
+            pending: None,
+            bookmark: None,
+        }
+    }
+
+    /// The field names of the records.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// The bookmark of the finished stream. Is `None` as long as the stream is not finished.
+    pub fn bookmark(&self) -> Option<&Bookmark> {
+        self.bookmark.as_ref()
+    }
+
+    /// Checks if the server has no more records for this stream; there might be records left in
+    /// the buffer though.
+    pub fn is_finished(&self) -> bool {
+        self.cursor.is_none() && self.pending.is_none()
+    }
+
+    /// Discards all records which were not consumed yet, i.e. the buffered records and every
+    /// record still on the server, which is told so by a `DISCARD`. Returns the bookmark of the
+    /// finished stream.
+    pub async fn discard(mut self) -> Result<Option<Bookmark>, ClientError> {
+        self.buffer.clear();
+
+        // a running pull has to be finished first:
+        if let Some(pending) = self.pending.take() {
+            let (cursor, result) = pending.await;
+            match result? {
+                StreamResult::HasMore(_) => self.cursor = Some(cursor),
+                StreamResult::Finished(success, _) =>
+                    self.bookmark = Bookmark::from_success(success),
+                StreamResult::Ignored =>
+                    return Err(ConnectionError::UnexpectedResponse.into()),
+            }
+        }
+
+        if let Some(mut cursor) = self.cursor.take() {
+            let success = cursor.connection.discard(Amount::All, cursor.qid).await?;
+            self.bookmark = Bookmark::from_success(success);
+        }
+
+        Ok(self.bookmark)
+    }
+
+    fn pull(mut cursor: Cursor) -> PendingPull {
+        Box::pin(async move {
+            let result = cursor.connection.pull(cursor.batch, cursor.qid).await;
+            (cursor, result)
+        })
+    }
+}
+
+impl Stream for RecordStream {
+    type Item = Result<RecordResult, ClientError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(record) = this.buffer.pop_front() {
+                return Poll::Ready(Some(RecordResult::with_fields(this.fields.clone(), record)));
+            }
+
+            if let Some(pending) = this.pending.as_mut() {
+                let (cursor, result) = match pending.as_mut().poll(cx) {
+                    Poll::Ready(r) => r,
+                    Poll::Pending => return Poll::Pending,
+                };
+                this.pending = None;
+
+                match result {
+                    Ok(StreamResult::HasMore(records)) => {
+                        this.buffer.extend(records);
+                        this.cursor = Some(cursor);
+                    }
+                    Ok(StreamResult::Finished(success, records)) => {
+                        this.buffer.extend(records);
+                        this.bookmark = Bookmark::from_success(success);
+                    }
+                    Ok(StreamResult::Ignored) =>
+                        return Poll::Ready(Some(Err(ConnectionError::UnexpectedResponse.into()))),
+                    Err(e) =>
+                        return Poll::Ready(Some(Err(e.into()))),
+                }
+
+                continue;
+            }
+
+            match this.cursor.take() {
+                Some(cursor) => this.pending = Some(RecordStream::pull(cursor)),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
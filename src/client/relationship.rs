@@ -0,0 +1,85 @@
+use packs::std_structs::{Path, Relationship, UnboundRelationship};
+
+use crate::client::path::PathExt;
+
+/// Resolves the endpoints of a relationship relative to one of its nodes:
+/// ```
+/// # use raio::client::relationship::RelationshipExt;
+/// # use packs::Dictionary;
+/// # use packs::std_structs::Relationship;
+/// let rel = Relationship { id: 7, start_node_id: 1, end_node_id: 2, _type: String::from("KNOWS"), properties: Dictionary::new() };
+///
+/// assert_eq!(rel.other_node(1), Some(2));
+/// assert_eq!(rel.other_node(3), None);
+/// assert!(rel.is_outgoing_from(1));
+/// assert!(rel.is_incoming_to(2));
+/// ```
+pub trait RelationshipExt {
+    /// The id of the node at the other end, if `node_id` is one of the endpoints. For a loop this
+    /// is `node_id` itself.
+    fn other_node(&self, node_id: i64) -> Option<i64>;
+
+    fn is_outgoing_from(&self, node_id: i64) -> bool;
+
+    fn is_incoming_to(&self, node_id: i64) -> bool;
+}
+
+impl RelationshipExt for Relationship {
+    fn other_node(&self, node_id: i64) -> Option<i64> {
+        if self.start_node_id == node_id {
+            Some(self.end_node_id)
+        } else if self.end_node_id == node_id {
+            Some(self.start_node_id)
+        } else {
+            None
+        }
+    }
+
+    fn is_outgoing_from(&self, node_id: i64) -> bool {
+        self.start_node_id == node_id
+    }
+
+    fn is_incoming_to(&self, node_id: i64) -> bool {
+        self.end_node_id == node_id
+    }
+}
+
+/// Binds relationships of a path, which come without endpoints, to their start and end node:
+/// ```
+/// # use raio::client::relationship::UnboundRelationshipExt;
+/// # use packs::Dictionary;
+/// # use packs::std_structs::{Node, Path, UnboundRelationship};
+/// let knows = UnboundRelationship { id: 10, _type: String::from("KNOWS"), properties: Dictionary::new() };
+/// // (1)<-[:KNOWS]-(2):
+/// let path = Path { nodes: vec![Node::new(1), Node::new(2)], rels: vec![knows.clone()], ids: vec![-1, 1] };
+///
+/// let bound = knows.bind_in(&path).unwrap();
+/// assert_eq!((bound.start_node_id, bound.end_node_id), (2, 1));
+/// assert_eq!(knows.bind(1, 2).start_node_id, 1);
+/// ```
+pub trait UnboundRelationshipExt {
+    fn bind(&self, start_node_id: i64, end_node_id: i64) -> Relationship;
+
+    /// Binds the relationship with the endpoints it has in `path`, or `None` if it is not
+    /// traversed by `path`.
+    fn bind_in(&self, path: &Path) -> Option<Relationship>;
+}
+
+impl UnboundRelationshipExt for UnboundRelationship {
+    fn bind(&self, start_node_id: i64, end_node_id: i64) -> Relationship {
+        Relationship {
+            id: self.id,
+            start_node_id,
+            end_node_id,
+            _type: self._type.clone(),
+            properties: self.properties.clone(),
+        }
+    }
+
+    fn bind_in(&self, path: &Path) -> Option<Relationship> {
+        path
+            .iter_segments()
+            .find(|s| s.relationship.id == self.id)
+            .map(|s| s.bound_relationship())
+    }
+}
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// Controls how managed transactions (c.f. [`write_transaction`](crate::client::Client::write_transaction))
+/// are retried on transient errors: the first retry waits `initial_delay`, every further retry
+/// waits `multiplier` times longer than the one before, but at most `max_delay`. No retry is
+/// started after `max_retry_time` has passed since the first attempt.
+pub struct RetryConfig {
+    pub max_retry_time: Duration,
+    pub initial_delay: Duration,
+    pub multiplier: f64,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            max_retry_time: Duration::from_secs(30),
+            initial_delay: Duration::from_secs(1),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// A config which never retries.
+    pub fn none() -> Self {
+        RetryConfig::default().max_retry_time(Duration::from_secs(0))
+    }
+
+    pub fn max_retry_time(mut self, max_retry_time: Duration) -> Self {
+        self.max_retry_time = max_retry_time;
+        self
+    }
+
+    pub fn initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// The delay to wait before the next retry, given the current one:
+    /// ```
+    /// # use raio::client::retry::RetryConfig;
+    /// # use std::time::Duration;
+    /// let retry = RetryConfig::default().multiplier(3.0).max_delay(Duration::from_secs(10));
+    /// assert_eq!(retry.next_delay(Duration::from_secs(2)), Duration::from_secs(6));
+    /// assert_eq!(retry.next_delay(Duration::from_secs(6)), Duration::from_secs(10));
+    /// assert_eq!(retry.multiplier(f64::MAX).next_delay(Duration::from_secs(6)), Duration::from_secs(10));
+    /// assert_eq!(retry.multiplier(-1.0).next_delay(Duration::from_secs(6)), Duration::from_secs(0));
+    /// ```
+    pub fn next_delay(&self, delay: Duration) -> Duration {
+        let secs = delay.as_secs_f64() * self.multiplier;
+        if !secs.is_finite() || secs >= self.max_delay.as_secs_f64() {
+            self.max_delay
+        } else {
+            Duration::from_secs_f64(secs.max(0.0))
+        }
+    }
+}
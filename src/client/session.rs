@@ -0,0 +1,162 @@
+use crate::client::Client;
+use crate::client::auto_commit::{AutoCommit, AutoCommitResult};
+use crate::client::error::ClientError;
+use crate::client::summary::ServerInfo;
+use crate::client::bookmark_manager::BookmarkManager;
+use std::sync::Arc;
+use crate::client::transaction::Transaction;
+use crate::messaging::bookmark::Bookmark;
+use crate::messaging::commit_prepare::{CommitPrepare, CommitMode};
+use crate::messaging::query::Query;
+
+/// A `Session` is a causally chained sequence of auto-commits and transactions on a
+/// [`Client`](crate::client::Client). It keeps the bookmark of the last finished auto-commit or
+/// transaction and sends it along with the next one, such that every query sees the changes of
+/// the queries before. Database and access mode set on the session are used for every query.
+/// ```no_run
+/// # use raio::client::{Client, ClientConfig};
+/// # use raio::client::auth::Basic;
+/// # use raio::messaging::query::Query;
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), raio::client::error::ClientError> {
+/// # let client = Client::create("localhost:7687", Basic::new("neo4j", "neo4j"), ClientConfig::default("doc", "0.1"));
+/// let mut session = client.session().db("movies");
+///
+/// session.run(&Query::new("CREATE (:Person {name: 'Jane'})")).await?;
+///
+/// // sees the created person:
+/// let mut transaction = session.begin().await?;
+/// transaction.run(&Query::new("MATCH (p:Person) RETURN p.name as name")).await?;
+/// session.commit(transaction).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Session<'c> {
+    client: &'c Client,
+    db: Option<String>,
+    mode: Option<CommitMode>,
+    bookmarks: Vec<Bookmark>,
+    bookmark_manager: Option<Arc<dyn BookmarkManager>>,
+    /// The bookmarks the currently open transaction waited for.
+    pending: Vec<Bookmark>,
+}
+
+impl<'c> Session<'c> {
+    pub(crate) fn new(client: &'c Client) -> Self {
+        Session {
+            client,
+            db: None,
+            mode: None,
+            bookmarks: Vec::new(),
+            bookmark_manager: client.bookmark_manager.clone(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Information about the server of the client, see
+    /// [`Client::server_info`](crate::client::Client::server_info).
+    pub async fn server_info(&self) -> Result<ServerInfo, ClientError> {
+        self.client.server_info().await
+    }
+
+    /// Sets the database used by all queries of the session.
+    pub fn db(mut self, db_name: &str) -> Self {
+        self.db = Some(String::from(db_name));
+        self
+    }
+
+    /// Sets the access mode used by all queries of the session, unless an auto-commit sets its
+    /// own, see [`run_with`](crate::client::session::Session::run_with).
+    pub fn mode(mut self, mode: CommitMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Starts the session after the provided bookmarks.
+    pub fn after(mut self, bookmarks: Vec<Bookmark>) -> Self {
+        self.bookmarks = bookmarks;
+        self
+    }
+
+    /// Shares the bookmarks of the session with other sessions using `manager`: every query also
+    /// waits for the bookmarks of the manager and hands its own bookmark to it.
+    pub fn bookmark_manager(mut self, manager: Arc<dyn BookmarkManager>) -> Self {
+        self.bookmark_manager = Some(manager);
+        self
+    }
+
+    /// The bookmarks of the session itself, which the next query waits for.
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    /// Runs the provided query as an auto-commit after the last bookmark of the session.
+    pub async fn run(&mut self, query: &Query) -> Result<AutoCommitResult, ClientError> {
+        self.run_with(AutoCommit::new(query)).await
+    }
+
+    /// Runs an `AutoCommit` after the last bookmark of the session. Its own access mode, e.g. set
+    /// by [`write`](crate::client::auto_commit::AutoCommit::write), takes precedence over the one
+    /// of the session.
+    pub async fn run_with(&mut self, mut auto_commit: AutoCommit<'_>) -> Result<AutoCommitResult, ClientError> {
+        let previous = self.prepare(auto_commit.prepare());
+
+        let result = self.client.run(&auto_commit).await?;
+        if let Some(bookmark) = result.bookmark() {
+            self.finished(&previous, bookmark.clone());
+        }
+        Ok(result)
+    }
+
+    /// Opens a transaction after the last bookmark of the session. Use
+    /// [`commit`](crate::client::session::Session::commit) of the session to chain its bookmark.
+    pub async fn begin(&mut self) -> Result<Transaction, ClientError> {
+        let mut settings = CommitPrepare::new();
+        self.pending = self.prepare(&mut settings);
+        self.client.begin(settings).await
+    }
+
+    /// Commits the transaction and keeps its bookmark for the next query of the session.
+    pub async fn commit(&mut self, transaction: Transaction) -> Result<(), ClientError> {
+        let outcome = transaction.commit().await?;
+        let previous = std::mem::take(&mut self.pending);
+        if let Some(bookmark) = outcome.bookmark {
+            self.finished(&previous, bookmark);
+        }
+        Ok(())
+    }
+
+    /// Sets mode, unless already set, database and bookmarks; returns the bookmarks the query
+    /// waits for.
+    fn prepare(&self, settings: &mut CommitPrepare) -> Vec<Bookmark> {
+        if settings.mode.is_none() {
+            settings.set_mode(self.mode);
+        }
+        if let Some(db) = &self.db {
+            settings.set_db(db);
+        }
+
+        let mut bookmarks = self.bookmarks.clone();
+        if let Some(manager) = &self.bookmark_manager {
+            for bookmark in manager.bookmarks() {
+                if !bookmarks.contains(&bookmark) {
+                    bookmarks.push(bookmark);
+                }
+            }
+        }
+
+        for bookmark in bookmarks.iter() {
+            settings.add_bookmark(bookmark.clone());
+        }
+
+        bookmarks
+    }
+
+    fn finished(&mut self, previous: &[Bookmark], bookmark: Bookmark) {
+        if let Some(manager) = &self.bookmark_manager {
+            manager.update_bookmarks(previous, &bookmark);
+        }
+
+        self.bookmarks = vec![bookmark];
+    }
+}
@@ -0,0 +1,223 @@
+use std::ops::AddAssign;
+use std::time::Duration;
+
+use packs::{Dictionary, extract_list};
+use packs::std_structs::StdStructPrimitive;
+
+use crate::connectivity::connection::Connection;
+use crate::connectivity::version::Version;
+use crate::messaging::response::Success;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// The kind of a query as reported by the server.
+pub enum QueryType {
+    Read,
+    Write,
+    ReadWrite,
+    Schema,
+}
+
+impl QueryType {
+    fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "r" => Some(QueryType::Read),
+            "w" => Some(QueryType::Write),
+            "rw" => Some(QueryType::ReadWrite),
+            "s" => Some(QueryType::Schema),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+/// The update statistics of a query; all counters are `0` if the server did not report any.
+pub struct Counters {
+    pub nodes_created: i64,
+    pub nodes_deleted: i64,
+    pub relationships_created: i64,
+    pub relationships_deleted: i64,
+    pub properties_set: i64,
+    pub labels_added: i64,
+    pub labels_removed: i64,
+    pub indexes_added: i64,
+    pub indexes_removed: i64,
+    pub constraints_added: i64,
+    pub constraints_removed: i64,
+    pub system_updates: i64,
+}
+
+impl Counters {
+    fn from_stats(mut stats: Dictionary<StdStructPrimitive>) -> Self {
+        let mut counter = |key: &str| stats.extract_property_typed(key).unwrap_or(0);
+        Counters {
+            nodes_created: counter("nodes-created"),
+            nodes_deleted: counter("nodes-deleted"),
+            relationships_created: counter("relationships-created"),
+            relationships_deleted: counter("relationships-deleted"),
+            properties_set: counter("properties-set"),
+            labels_added: counter("labels-added"),
+            labels_removed: counter("labels-removed"),
+            indexes_added: counter("indexes-added"),
+            indexes_removed: counter("indexes-removed"),
+            constraints_added: counter("constraints-added"),
+            constraints_removed: counter("constraints-removed"),
+            system_updates: counter("system-updates"),
+        }
+    }
+
+    /// Checks if any data or schema was changed.
+    pub fn contains_updates(&self) -> bool {
+        self.nodes_created != 0
+            || self.nodes_deleted != 0
+            || self.relationships_created != 0
+            || self.relationships_deleted != 0
+            || self.properties_set != 0
+            || self.labels_added != 0
+            || self.labels_removed != 0
+            || self.indexes_added != 0
+            || self.indexes_removed != 0
+            || self.constraints_added != 0
+            || self.constraints_removed != 0
+    }
+}
+
+impl AddAssign for Counters {
+    fn add_assign(&mut self, other: Counters) {
+        self.nodes_created += other.nodes_created;
+        self.nodes_deleted += other.nodes_deleted;
+        self.relationships_created += other.relationships_created;
+        self.relationships_deleted += other.relationships_deleted;
+        self.properties_set += other.properties_set;
+        self.labels_added += other.labels_added;
+        self.labels_removed += other.labels_removed;
+        self.indexes_added += other.indexes_added;
+        self.indexes_removed += other.indexes_removed;
+        self.constraints_added += other.constraints_added;
+        self.constraints_removed += other.constraints_removed;
+        self.system_updates += other.system_updates;
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// The position in the query a notification refers to.
+pub struct InputPosition {
+    pub offset: i64,
+    pub line: i64,
+    pub column: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A notification of the server about a query, like a warning about a cartesian product or a
+/// deprecation notice.
+pub struct Notification {
+    pub code: String,
+    pub title: String,
+    pub description: String,
+    pub severity: String,
+    pub position: Option<InputPosition>,
+}
+
+impl Notification {
+    fn from_dictionary(mut dict: Dictionary<StdStructPrimitive>) -> Self {
+        let mut text = |key: &str| dict.extract_property_typed(key).unwrap_or_default();
+        let code = text("code");
+        let title = text("title");
+        let description = text("description");
+        let severity = text("severity");
+
+        let position: Option<Dictionary<StdStructPrimitive>> = dict.extract_property_typed("position");
+        let position = position.and_then(|mut p| {
+            Some(InputPosition {
+                offset: p.extract_property_typed("offset")?,
+                line: p.extract_property_typed("line")?,
+                column: p.extract_property_typed("column")?,
+            })
+        });
+
+        Notification {
+            code,
+            title,
+            description,
+            severity,
+            position,
+        }
+    }
+
+    /// Checks if the server flagged this notification as a warning.
+    pub fn is_warning(&self) -> bool {
+        self.severity == "WARNING"
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A summary of a finished query, which is built from the `SUCCESS` of the `RUN` and the
+/// `SUCCESS` which ends the stream of records.
+pub struct ResultSummary {
+    pub counters: Counters,
+    pub query_type: Option<QueryType>,
+    /// Milliseconds until the first record was available.
+    pub t_first: Option<i64>,
+    /// Milliseconds until the last record was consumed.
+    pub t_last: Option<i64>,
+    pub db: Option<String>,
+    pub notifications: Vec<Notification>,
+}
+
+impl ResultSummary {
+    /// Extracts the summary information out of the `SUCCESS` of the `RUN` and the `SUCCESS` of
+    /// the stream end.
+    pub fn from_success(stream_begin: &mut Success, stream_end: &mut Success) -> Self {
+        let stats: Option<Dictionary<StdStructPrimitive>> =
+            stream_end.metadata.extract_property_typed("stats");
+        let query_type: Option<String> = stream_end.metadata.extract_property_typed("type");
+        let notifications: Vec<Dictionary<StdStructPrimitive>> =
+            stream_end
+                .metadata
+                .extract_property("notifications")
+                .and_then(extract_list)
+                .unwrap_or_default();
+
+        ResultSummary {
+            counters: stats.map(Counters::from_stats).unwrap_or_default(),
+            query_type: query_type.as_deref().and_then(QueryType::from_code),
+            t_first: stream_begin.metadata.extract_property_typed("t_first"),
+            t_last: stream_end.metadata.extract_property_typed("t_last"),
+            db: stream_end.metadata.extract_property_typed("db"),
+            notifications: notifications.into_iter().map(Notification::from_dictionary).collect(),
+        }
+    }
+
+    /// All notifications which are warnings.
+    pub fn warnings(&self) -> impl Iterator<Item = &Notification> {
+        self.notifications.iter().filter(|n| n.is_warning())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The server a connection is established to, as reported in its handshake and `HELLO`.
+pub struct ServerInfo {
+    /// The agent of the server, like `Neo4j/4.1.0`.
+    pub agent: Option<String>,
+    /// The id the server assigned to the connection, like `bolt-42`.
+    pub connection_id: Option<String>,
+    pub protocol_version: Option<Version>,
+}
+
+impl ServerInfo {
+    pub(crate) fn from_connection(connection: &Connection) -> Self {
+        ServerInfo {
+            agent: connection.server_agent().map(String::from),
+            connection_id: connection.connection_id().map(String::from),
+            protocol_version: connection.version(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The outcome of a successful connectivity check, see
+/// [`Client::verify_connectivity`](crate::client::Client::verify_connectivity).
+pub struct Connectivity {
+    pub server: ServerInfo,
+    /// The time of a single round trip to the server.
+    pub latency: Duration,
+}
@@ -1,18 +1,71 @@
-use deadpool::managed::Object;
-use crate::connectivity::connection::{Connection, ConnectionError};
+use crate::connectivity::connection::{ConnectionError, State};
+use crate::connectivity::pool::PooledConnection;
 use crate::messaging::query::Query;
 use crate::client::error::ClientError;
-use crate::client::record_result::RecordResult;
+use crate::client::record_result::{RecordResult, FromRecord};
 use crate::messaging::request::{Run, Amount, Qid, Commit, RollBack};
 use crate::connectivity::stream_result::StreamResult;
 use crate::messaging::bookmark::Bookmark;
+use crate::client::summary::{Counters, ResultSummary};
+use crate::client::graph::Graph;
+use crate::client::metrics::Instruments;
+use crate::client::Client;
+use crate::messaging::commit_prepare::{CommitPrepare, CommitMode};
+use crate::messaging::response::{Record, Success};
+use futures::Stream;
+use packs::{Dictionary, Value};
+use packs::std_structs::StdStruct;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
 
+/// An explicit transaction. A transaction which is dropped without `commit` or `rollback` is
+/// rolled back by the server, since its connection is reset before it is used again.
 pub struct Transaction {
-    pub(crate) connection: Object<Connection, ConnectionError>
+    pub(crate) connection: PooledConnection,
+    pub(crate) fetch_size: Amount,
+    pub(crate) instruments: Instruments,
+    pub(crate) open: bool,
+    pub(crate) counters: Counters,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The outcome of a committed transaction.
+pub struct CommitOutcome {
+    /// The bookmark of the transaction; the server might not send one, e.g. for read-only
+    /// transactions.
+    pub bookmark: Option<Bookmark>,
+    /// A summary of the transaction, whose counters sum up the counters of its queries.
+    pub summary: ResultSummary,
 }
 
 impl Transaction {
-    pub async fn run(&mut self, query: &Query) -> Result<Vec<RecordResult>, ClientError> {
+    /// Checks if the transaction is still open on the server, i.e. it was neither committed nor
+    /// rolled back and none of its queries failed, which terminates the transaction.
+    pub fn is_open(&self) -> bool {
+        self.open && self.connection.state() == State::Ready
+    }
+
+    /// Fails with [`TransactionClosed`](crate::client::error::ClientError::TransactionClosed)
+    /// instead of sending a request which the server would ignore.
+    fn ensure_open(&self) -> Result<(), ClientError> {
+        if self.is_open() {
+            Ok(())
+        } else {
+            Err(ClientError::TransactionClosed)
+        }
+    }
+
+    pub async fn run(&mut self, query: &Query) -> Result<TransactionResult, ClientError> {
+        let started = self.instruments.query_started(query);
+        let result = self.run_query(query).await;
+        self.instruments.query_finished(query, started, result.as_ref().map(|r| r.records.len()));
+
+        result
+    }
+
+    async fn run_query(&mut self, query: &Query) -> Result<TransactionResult, ClientError> {
+        self.ensure_open()?;
         self.connection.send(&Run::new(query)).await?;
         let mut run_success = self.connection.recv_success().await?;
         
@@ -22,25 +75,292 @@ impl Transaction {
             run_success.extract_fields().ok_or(ClientError::NoFieldInformation)?;
         
         let pull_result =
-            self.connection.pull(Amount::All, Qid::Exact(qid)).await?;
+            self.connection.pull_batched(self.fetch_size, Qid::Exact(qid)).await?;
         
         match pull_result {
-            StreamResult::Finished(_, records) => 
-                RecordResult::from_results(&fields, records),
+            StreamResult::Finished(mut stream_end, records) => {
+                let summary = ResultSummary::from_success(&mut run_success, &mut stream_end);
+                self.counters += summary.counters;
+                Ok(TransactionResult {
+                    records: RecordResult::from_results(&fields, records)?,
+                    summary,
+                })
+            }
             
             _ => Err(ClientError::StreamStillOpen)
         }
     }
     
-    pub async fn commit(mut self) -> Result<Bookmark, ClientError> {
+    /// Runs the query but pulls its records lazily in batches of the fetch size, as they are
+    /// consumed from the returned cursor. The transaction can be used again as soon as the
+    /// cursor is dropped; records not consumed by then are discarded by the server at the end
+    /// of the transaction:
+    /// ```no_run
+    /// # use raio::client::{Client, ClientConfig};
+    /// # use raio::client::auth::Basic;
+    /// # use raio::messaging::commit_prepare::CommitPrepare;
+    /// # use raio::messaging::query::Query;
+    /// # #[async_std::main]
+    /// # async fn main() -> Result<(), raio::client::error::ClientError> {
+    /// # let client = Client::create("localhost:7687", Basic::new("neo4j", "neo4j"), ClientConfig::default("doc", "0.1"));
+    /// let mut transaction = client.begin(CommitPrepare::new()).await?;
+    ///
+    /// let mut cursor = transaction.run_stream(&Query::new("UNWIND range(1, 100000) as x RETURN x")).await?;
+    /// let mut sum = 0;
+    /// while let Some(record) = cursor.next().await {
+    ///     sum += record?.get_field_typed::<i64>("x").unwrap();
+    /// }
+    ///
+    /// transaction.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn run_stream(&mut self, query: &Query) -> Result<TransactionCursor<'_>, ClientError> {
+        self.ensure_open()?;
+        self.connection.send(&Run::new(query)).await?;
+        let mut run_success = self.connection.recv_success().await?;
+
+        let qid =
+            run_success.extract_qid().ok_or(ClientError::NoQidInformation)?;
+        let fields =
+            run_success.extract_fields().ok_or(ClientError::NoFieldInformation)?;
+
+        Ok(TransactionCursor {
+            transaction: self,
+            fields: fields.into(),
+            qid: Qid::Exact(qid),
+            buffer: VecDeque::new(),
+            run_success,
+            summary: None,
+        })
+    }
+
+    /// Runs the query but discards all of its records, which is cheaper for queries whose
+    /// records are not needed, like most writes. Returns the summary of the query.
+    pub async fn execute(&mut self, query: &Query) -> Result<ResultSummary, ClientError> {
+        self.ensure_open()?;
+        self.connection.send(&Run::new(query)).await?;
+        let mut run_success = self.connection.recv_success().await?;
+
+        let qid =
+            run_success.extract_qid().ok_or(ClientError::NoQidInformation)?;
+
+        let mut stream_end = self.connection.discard(Amount::All, Qid::Exact(qid)).await?;
+        let summary = ResultSummary::from_success(&mut run_success, &mut stream_end);
+        self.counters += summary.counters;
+        Ok(summary)
+    }
+
+    /// Sends a `DISCARD` for all remaining records of the query with the provided id.
+    pub async fn discard(&mut self, qid: Qid) -> Result<(), ClientError> {
+        self.ensure_open()?;
+        let _ = self.connection.discard(Amount::All, qid).await?;
+        Ok(())
+    }
+
+    pub async fn commit(mut self) -> Result<CommitOutcome, ClientError> {
+        self.ensure_open()?;
         self.connection.send(&Commit {}).await?;
-        Bookmark::from_success(
-            self.connection.recv_success().await?
-        )
+        let mut success = self.connection.recv_success().await?;
+        self.open = false;
+
+        let mut summary = ResultSummary::from_success(&mut Success { metadata: Dictionary::new() }, &mut success);
+        summary.counters = self.counters;
+        Ok(CommitOutcome {
+            bookmark: Bookmark::from_success(success),
+            summary,
+        })
     }
     
+    /// Rolls back the transaction and waits for the server to confirm it. If a query of the
+    /// transaction has failed, the server has already terminated the transaction and it is only
+    /// reset.
     pub async fn rollback(mut self) -> Result<(), ClientError> {
+        if self.connection.state() == State::Failed {
+            self.connection.recover().await?;
+            self.open = false;
+            return Ok(());
+        }
+
         self.connection.send(&RollBack {}).await?;
+        let _ = self.connection.recv_success().await?;
+        self.open = false;
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        if self.open {
+            self.connection.mark_for_reset();
+        }
+    }
+}
+
+/// Collects the settings of a transaction before it is begun, see
+/// [`Client::transaction`](crate::client::Client::transaction):
+/// ```no_run
+/// # use std::time::Duration;
+/// # use raio::client::{Client, ClientConfig};
+/// # use raio::client::auth::Basic;
+/// # use raio::messaging::query::Query;
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), raio::client::error::ClientError> {
+/// # let client = Client::create("localhost:7687", Basic::new("neo4j", "neo4j"), ClientConfig::default("doc", "0.1"));
+/// let mut transaction =
+///     client
+///         .transaction()
+///         .with_timeout(Duration::from_secs(5))
+///         .with_metadata("app", "reporting")
+///         .read_only()
+///         .on_db("movies")
+///         .begin()
+///         .await?;
+///
+/// transaction.run(&Query::new("MATCH (m:Movie) RETURN count(m) as n")).await?;
+/// transaction.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TransactionBuilder<'c> {
+    client: &'c Client,
+    settings: CommitPrepare,
+}
+
+impl<'c> TransactionBuilder<'c> {
+    pub(crate) fn new(client: &'c Client) -> Self {
+        TransactionBuilder {
+            client,
+            settings: CommitPrepare::new(),
+        }
+    }
+
+    /// Lets the server terminate the transaction if it runs longer than `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        // `tx_timeout` is sent in milliseconds:
+        self.settings.set_timeout(Some(timeout.as_millis() as i64));
+        self
+    }
+
+    /// Attaches metadata to the transaction, which shows up in the query log and in
+    /// `SHOW TRANSACTIONS`.
+    pub fn with_metadata<V: Into<Value<StdStruct>>>(mut self, key: &str, value: V) -> Self {
+        self.settings.metadata().add_property(key, value);
+        self
+    }
+
+    pub fn read_only(mut self) -> Self {
+        self.settings.set_mode(Some(CommitMode::Read));
+        self
+    }
+
+    pub fn on_db(mut self, db_name: &str) -> Self {
+        self.settings.set_db(db_name);
+        self
+    }
+
+    /// Begins the transaction after the provided bookmark.
+    pub fn after(mut self, bookmark: Bookmark) -> Self {
+        self.settings.add_bookmark(bookmark);
+        self
+    }
+
+    pub fn impersonate(mut self, user: &str) -> Self {
+        self.settings.set_impersonated_user(Some(user));
+        self
+    }
+
+    pub async fn begin(self) -> Result<Transaction, ClientError> {
+        self.client.begin(self.settings).await
+    }
+}
+
+/// The records of a query run by
+/// [`Transaction::run_stream`](crate::client::transaction::Transaction::run_stream), which are
+/// pulled in batches as they are consumed.
+pub struct TransactionCursor<'t> {
+    transaction: &'t mut Transaction,
+    fields: Arc<[String]>,
+    qid: Qid,
+    buffer: VecDeque<Record>,
+    run_success: Success,
+    summary: Option<ResultSummary>,
+}
+
+impl<'t> TransactionCursor<'t> {
+    /// The field names of the records.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// The next record; pulls the next batch if all records pulled so far are consumed.
+    pub async fn next(&mut self) -> Option<Result<RecordResult, ClientError>> {
+        loop {
+            if let Some(record) = self.buffer.pop_front() {
+                return Some(RecordResult::with_fields(self.fields.clone(), record));
+            }
+
+            if self.summary.is_some() {
+                return None;
+            }
+
+            let connection = &mut self.transaction.connection;
+            match connection.pull(self.transaction.fetch_size, self.qid).await {
+                Ok(StreamResult::HasMore(records)) =>
+                    self.buffer.extend(records),
+                Ok(StreamResult::Finished(mut stream_end, records)) => {
+                    self.buffer.extend(records);
+                    let summary = ResultSummary::from_success(&mut self.run_success, &mut stream_end);
+                    self.transaction.counters += summary.counters;
+                    self.summary = Some(summary);
+                }
+                Ok(StreamResult::Ignored) =>
+                    return Some(Err(ConnectionError::UnexpectedResponse.into())),
+                Err(e) =>
+                    return Some(Err(e.into())),
+            }
+        }
+    }
+
+    /// The summary of the query; is `None` as long as the server has more records.
+    pub fn summary(&self) -> Option<&ResultSummary> {
+        self.summary.as_ref()
+    }
+
+    /// Turns the cursor into a stream of its records.
+    pub fn into_stream(self) -> impl Stream<Item = Result<RecordResult, ClientError>> + 't {
+        futures::stream::unfold(self, |mut cursor| async move {
+            cursor.next().await.map(|record| (record, cursor))
+        })
+    }
+}
+
+/// The result of a query run within a transaction.
+pub struct TransactionResult {
+    records: Vec<RecordResult>,
+    summary: ResultSummary,
+}
+
+impl TransactionResult {
+    pub fn records(&self) -> &Vec<RecordResult> {
+        &self.records
+    }
+
+    pub fn into_records(self) -> Vec<RecordResult> {
+        self.records
+    }
+
+    /// Collects the distinct nodes and relationships of all records.
+    pub fn graph(&self) -> Graph {
+        Graph::from_records(&self.records)
+    }
+
+    /// Maps every record into a `T`, failing on the first record which does not fit.
+    pub fn into_typed<T: FromRecord>(self) -> Result<Vec<T>, ClientError> {
+        self.records.into_iter().map(T::from_record).collect()
+    }
+
+    pub fn summary(&self) -> &ResultSummary {
+        &self.summary
+    }
+}
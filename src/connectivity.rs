@@ -1,5 +1,11 @@
+pub mod backoff;
 pub mod connection;
 pub mod manager;
+pub mod pipeline;
 pub mod pool;
+pub mod shared;
+pub mod stream;
 pub mod version;
 pub mod stream_result;
+pub mod uri;
+pub mod wire_tap;
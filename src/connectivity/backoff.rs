@@ -0,0 +1,204 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::connectivity::connection::ConnectionError;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// Controls how a pool backs off from its server after failing to connect to it: after the n-th
+/// failure in a row, no connection is attempted for `initial_delay * multiplier^(n-1)`, at most
+/// `max_delay`, varied randomly by up to `jitter` (a fraction of the delay) in both directions.
+/// Requests for a new connection wait until then.
+///
+/// After `max_failures` failures in a row the circuit opens: requests fail right away with
+/// `ServerUnavailable` until the delay has passed, then a single attempt is let through, which
+/// closes the circuit again if it succeeds.
+pub struct BackoffConfig {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f64,
+    pub jitter: f64,
+    pub max_failures: usize,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        BackoffConfig {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_failures: 5,
+        }
+    }
+}
+
+impl BackoffConfig {
+    /// A config which never waits and never opens the circuit.
+    pub fn none() -> Self {
+        BackoffConfig::default()
+            .initial_delay(Duration::from_secs(0))
+            .max_delay(Duration::from_secs(0))
+            .max_failures(usize::MAX)
+    }
+
+    pub fn initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn max_failures(mut self, max_failures: usize) -> Self {
+        self.max_failures = max_failures;
+        self
+    }
+
+    /// The delay after `failures` failures in a row, without jitter:
+    /// ```
+    /// # use raio::connectivity::backoff::BackoffConfig;
+    /// # use std::time::Duration;
+    /// let backoff = BackoffConfig::default().max_delay(Duration::from_millis(300));
+    /// assert_eq!(backoff.delay(0), Duration::from_millis(0));
+    /// assert_eq!(backoff.delay(1), Duration::from_millis(100));
+    /// assert_eq!(backoff.delay(2), Duration::from_millis(200));
+    /// assert_eq!(backoff.delay(3), Duration::from_millis(300));
+    /// ```
+    pub fn delay(&self, failures: usize) -> Duration {
+        if failures == 0 {
+            return Duration::from_secs(0);
+        }
+
+        let exponent = (failures - 1).min(i32::MAX as usize) as i32;
+        let secs = self.initial_delay.as_secs_f64() * self.multiplier.powi(exponent);
+        if !secs.is_finite() || secs >= self.max_delay.as_secs_f64() {
+            self.max_delay
+        } else {
+            Duration::from_secs_f64(secs.max(0.0))
+        }
+    }
+
+    fn jittered(&self, delay: Duration) -> Duration {
+        // every `RandomState` is seeded randomly:
+        let random = (RandomState::new().build_hasher().finish() >> 11) as f64 / (1u64 << 53) as f64;
+        delay.mul_f64((1.0 + self.jitter * (2.0 * random - 1.0)).max(0.0))
+    }
+}
+
+/// Tracks the failed attempts to connect to a server, see
+/// [`BackoffConfig`](crate::connectivity::backoff::BackoffConfig).
+pub(crate) struct CircuitBreaker {
+    config: BackoffConfig,
+    state: Mutex<BreakerState>,
+}
+
+struct BreakerState {
+    failures: usize,
+    retry_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: BackoffConfig) -> Self {
+        CircuitBreaker {
+            config,
+            state: Mutex::new(BreakerState {
+                failures: 0,
+                retry_at: None,
+            }),
+        }
+    }
+
+    /// Waits until the next attempt is due; fails with `ServerUnavailable` if the circuit is open.
+    pub(crate) async fn before_attempt(&self) -> Result<(), ConnectionError> {
+        let wait = {
+            let mut state = self.state.lock().unwrap();
+            let now = Instant::now();
+            let open = state.failures >= self.config.max_failures;
+            match state.retry_at {
+                Some(retry_at) if retry_at > now && open =>
+                    return Err(ConnectionError::ServerUnavailable),
+                Some(retry_at) if retry_at > now =>
+                    retry_at - now,
+                Some(_) if open => {
+                    // let this attempt through, but no other until it failed or succeeded:
+                    state.retry_at = Some(now + self.config.delay(state.failures));
+                    return Ok(());
+                }
+                _ => return Ok(()),
+            }
+        };
+
+        async_std::task::sleep(wait).await;
+        Ok(())
+    }
+
+    pub(crate) fn succeeded(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.failures = 0;
+        state.retry_at = None;
+    }
+
+    pub(crate) fn failed(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.failures = state.failures.saturating_add(1);
+        state.retry_at = Some(Instant::now() + self.config.jittered(self.config.delay(state.failures)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_is_capped() {
+        let backoff = BackoffConfig::default().max_delay(Duration::from_secs(1));
+        assert_eq!(backoff.delay(usize::MAX), Duration::from_secs(1));
+        assert_eq!(backoff.multiplier(f64::MAX).delay(3), Duration::from_secs(1));
+        assert_eq!(backoff.multiplier(-2.0).delay(2), Duration::from_secs(0));
+        assert!(backoff.jittered(Duration::from_secs(1)) <= Duration::from_millis(1200));
+    }
+
+    #[async_std::test]
+    async fn breaker_opens_and_closes() {
+        let delay = Duration::from_millis(50);
+        let breaker =
+            CircuitBreaker::new(
+                BackoffConfig::default()
+                    .initial_delay(delay)
+                    .max_delay(delay)
+                    .jitter(0.0)
+                    .max_failures(2));
+
+        // the first failure delays the next attempt:
+        breaker.failed();
+        let started = Instant::now();
+        assert!(breaker.before_attempt().await.is_ok());
+        assert!(started.elapsed() >= Duration::from_millis(40));
+
+        // the second one opens the circuit:
+        breaker.failed();
+        assert!(matches!(breaker.before_attempt().await, Err(ConnectionError::ServerUnavailable)));
+
+        // after the delay, a single attempt is let through:
+        async_std::task::sleep(delay).await;
+        assert!(breaker.before_attempt().await.is_ok());
+        assert!(matches!(breaker.before_attempt().await, Err(ConnectionError::ServerUnavailable)));
+
+        breaker.succeeded();
+        assert!(breaker.before_attempt().await.is_ok());
+    }
+}
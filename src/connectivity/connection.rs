@@ -1,14 +1,26 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use async_std::future::timeout;
 use async_std::io::{BufReader, BufWriter};
-use async_std::net::{TcpStream, ToSocketAddrs};
 use async_std::prelude::*;
-use packs::{Pack, Unpack};
+use futures::io::{ReadHalf, WriteHalf};
+use packs::{Dictionary, Pack, Unpack, extract_list_ref};
 use thiserror::Error;
 
+use crate::client::auth::AuthProviderError;
+use crate::client::error::{AuthError, Neo4jError};
+use crate::connectivity::stream::{BoltStream, BoltTransport, Encryption};
+use crate::connectivity::pipeline::Pipeline;
 use crate::connectivity::stream_result::StreamResult;
-use crate::connectivity::version::Version;
+use crate::connectivity::version::{Version, SupportedVersions};
+use crate::connectivity::wire_tap::{Direction, Frame, WireTap};
 use crate::messaging::response::{Failure, Success, Response};
-use crate::messaging::request::{Hello, Pull, GoodBye, Reset, Amount, Qid};
-use crate::messaging::message::Message;
+use crate::messaging::request::{Hello, Pull, Discard, GoodBye, Reset, Amount, Qid, Route, Logon, Logoff, Run};
+use crate::messaging::query::Query;
+use crate::messaging::message::{Message, MessageTooLarge};
+use crate::messaging::routing_table::RoutingTable;
 
 #[derive(Debug, Error)]
 /// Possible connection errors, which can happen during connecting, receiving or sending. It also
@@ -21,18 +33,28 @@ pub enum ConnectionError {
     #[error("Cannot unpack message: {0}")]
     UnpackingError(#[from] packs::DecodeError),
     #[error("None of {0:?} are supported by the server.")]
-    VersionsNotSupportedByServer([Version; 4]),
-    #[error("Authentication failed with code '{1}': {0}")]
-    AuthenticationError(String, String),
+    VersionsNotSupportedByServer(SupportedVersions),
+    #[error("{0}")]
+    AuthenticationError(AuthError),
+    #[error("{0}")]
+    AuthProviderError(#[from] AuthProviderError),
     #[error("Unexpected response")]
     UnexpectedResponse,
-    #[error("Failure response '{0}' with message: '{1}")]
-    FailureResponse(String, String),
+    #[error("Failure response {0}")]
+    FailureResponse(Neo4jError),
+    #[error("No routing table information in ROUTE response")]
+    NoRoutingTableInformation,
+    #[error("Timed out")]
+    Timeout,
+    #[error("Server unavailable after repeated connection failures")]
+    ServerUnavailable,
+    #[error("Received message exceeds the maximum size of {0} bytes")]
+    MessageTooLarge(usize),
 }
 
 impl From<Failure> for ConnectionError {
-    fn from(mut f: Failure) -> Self {
-        ConnectionError::FailureResponse(f.code().clone(), f.message().clone())
+    fn from(f: Failure) -> Self {
+        ConnectionError::FailureResponse(f.into())
     }
 }
 
@@ -40,6 +62,15 @@ impl From<Failure> for ConnectionError {
 pub struct ConnectionConfig {
     initial_chunks: usize,
     chunk_capacity: u16,
+    grow_chunks: bool,
+    encryption: Encryption,
+    versions: SupportedVersions,
+    max_idle: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    handshake_timeout: Option<Duration>,
+    recv_timeout: Option<Duration>,
+    max_message_size: Option<usize>,
+    utc_patch: bool,
 }
 
 impl ConnectionConfig {
@@ -47,6 +78,15 @@ impl ConnectionConfig {
         ConnectionConfig {
             initial_chunks: 1,
             chunk_capacity: 1400,
+            grow_chunks: false,
+            encryption: Encryption::Plain,
+            versions: SupportedVersions::default(),
+            max_idle: None,
+            connect_timeout: Some(Duration::from_secs(30)),
+            handshake_timeout: None,
+            recv_timeout: None,
+            max_message_size: None,
+            utc_patch: false,
         }
     }
 
@@ -59,63 +99,248 @@ impl ConnectionConfig {
         self.chunk_capacity = n;
         self
     }
+
+    /// Lets the chunks of large requests grow beyond `chunk_capacity` up to the maximal chunk
+    /// size, see [`Message::grow_chunks`](crate::messaging::message::Message::grow_chunks).
+    /// Disabled by default.
+    pub fn grow_chunks(mut self, grow: bool) -> Self {
+        self.grow_chunks = grow;
+        self
+    }
+
+    /// Sets the encryption of the connection; defaults to `Encryption::Plain`.
+    pub fn encryption(mut self, encryption: Encryption) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
+    /// Sets the versions proposed in the handshake.
+    pub fn versions(mut self, versions: SupportedVersions) -> Self {
+        self.versions = versions;
+        self
+    }
+
+    pub fn supported_versions(&self) -> SupportedVersions {
+        self.versions
+    }
+
+    /// Sets how long a connection might be idle before its liveness is verified by running
+    /// `RETURN 1` when it is taken out of the pool again. By default, only a `RESET` is sent.
+    /// The `RESET`s of a client's `keep_alive` count as use, such that a keep-alive interval
+    /// shorter than `max_idle` suppresses this check.
+    pub fn max_connection_idle(mut self, max_idle: Duration) -> Self {
+        self.max_idle = Some(max_idle);
+        self
+    }
+
+    pub fn connection_idle(&self) -> Option<Duration> {
+        self.max_idle
+    }
+
+    /// Sets how long connecting to each address the host resolves to, and the TLS handshake,
+    /// might take; defaults to 30 seconds. `None` waits forever.
+    pub fn connect_timeout(mut self, connect_timeout: Option<Duration>) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// Sets how long the bolt handshake might take; by default, it waits forever.
+    pub fn handshake_timeout(mut self, handshake_timeout: Option<Duration>) -> Self {
+        self.handshake_timeout = handshake_timeout;
+        self
+    }
+
+    /// Sets how long receiving a single response might take; by default, it waits forever.
+    pub fn recv_timeout(mut self, recv_timeout: Option<Duration>) -> Self {
+        self.recv_timeout = recv_timeout;
+        self
+    }
+
+    /// Sets the maximal size in bytes of a received message; a larger message fails with
+    /// `MessageTooLarge` and closes the connection. By default, the size is not limited.
+    pub fn max_message_size(mut self, max_message_size: Option<usize>) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// Asks 4.3 and 4.4 servers in `HELLO` to encode date times with zone in UTC, as servers
+    /// since 5.0 always do. Decoding the UTC date time structures is up to `packs`. Disabled by
+    /// default.
+    pub fn utc_patch(mut self, utc_patch: bool) -> Self {
+        self.utc_patch = utc_patch;
+        self
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum State {
     Connected,
     Ready,
+    /// A request failed, hence the server ignores all further requests until a `RESET`, see
+    /// [`Connection::recover`]. Pooled connections are reset when they are recycled. Also marks
+    /// connections which have to be reset for other reasons, see [`Connection::mark_for_reset`].
+    Failed,
     Closed,
 }
 
 /// A `Connection` is the low level abstraction of a bolt protocol connection. It takes care of the
 /// sending and receiving of [`Request`](crate::client::request) and [`Response`](crate::client::response::Response)
 /// by encoding and packing any request into a [`Message`](crate::connectivity::message::Message) and vice versa.
-pub struct Connection {
-    reader: BufReader<TcpStream>,
-    writer: BufWriter<TcpStream>,
+/// It speaks over any [`BoltTransport`](crate::connectivity::stream::BoltTransport), by default
+/// a TCP or TLS [`BoltStream`](crate::connectivity::stream::BoltStream).
+pub struct Connection<S: BoltTransport = BoltStream> {
+    reader: BufReader<ReadHalf<S>>,
+    writer: BufWriter<WriteHalf<S>>,
     config: ConnectionConfig,
     state: State,
+    version: Option<Version>,
+    created: Instant,
+    last_used: Instant,
+    send_buffer: Message,
+    recv_buffer: Message,
+    routing_context: Option<HashMap<String, String>>,
+    server_agent: Option<String>,
+    connection_id: Option<String>,
+    utc_patched: bool,
+    // the number of sent requests whose summary was not received yet:
+    pending: usize,
+    wire_tap: Option<Arc<dyn WireTap>>,
 }
 
 impl Connection {
-    pub fn state(&self) -> State {
-        self.state
+    /// Connects to provided address `host:port` and returns this established connection. The
+    /// host is resolved anew and all its addresses are tried in order. If encryption is
+    /// configured, the TLS handshake is done as well. Does **not** send or receive
+    /// anything of the bolt protocol.
+    pub async fn connect(addr: &str, config: ConnectionConfig) -> Result<Connection, ConnectionError> {
+        let stream =
+            BoltStream::connect_timeout(addr, config.encryption, config.connect_timeout)
+                .await
+                .map_err(|e| match e.kind() {
+                    std::io::ErrorKind::TimedOut => ConnectionError::Timeout,
+                    _ => ConnectionError::IOError(e),
+                })?;
+        Ok(Connection::from_transport(stream, config))
     }
+}
 
-    /// Connects to provided address and returns this established connection. Does **not** send or
-    /// receive anything.
-    pub async fn connect<A: ToSocketAddrs>(addr: A, config: ConnectionConfig) -> Result<Connection, ConnectionError> {
-        let stream = TcpStream::connect(addr).await?;
-        let reader = BufReader::new(stream.clone());
-        let writer = BufWriter::new(stream);
-        Ok(Connection {
-            reader,
-            writer,
+impl<S: BoltTransport> Connection<S> {
+    /// Creates a connection on top of an already established transport. Does **not** send or
+    /// receive anything of the bolt protocol.
+    pub fn from_transport(transport: S, config: ConnectionConfig) -> Self {
+        let (reader, writer) = futures::io::AsyncReadExt::split(transport);
+        Connection {
+            reader: BufReader::new(reader),
+            writer: BufWriter::new(writer),
             config,
             state: State::Connected,
-        })
+            version: None,
+            created: Instant::now(),
+            last_used: Instant::now(),
+            send_buffer:
+                Message::new_alloc(config.initial_chunks, config.chunk_capacity)
+                    .grow_chunks(config.grow_chunks),
+            recv_buffer: Message::new_alloc(0, config.chunk_capacity),
+            routing_context: None,
+            server_agent: None,
+            connection_id: None,
+            utc_patched: false,
+            pending: 0,
+            wire_tap: None,
+        }
     }
 
-    /// Performs a handshake as specified in the bolt protocol. A successful handshake ends in a
-    /// negotiated version between the client and a server.
-    pub async fn handshake(&mut self, versions: &[Version; 4]) -> Result<Version, ConnectionError> {
-        self.writer.write(&[0x60, 0x60, 0xB0, 0x17]).await?;
-        for v in versions {
-            self.writer.write(&v.encode()).await?;
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// The number of requests sent on this connection whose summary, i.e. `SUCCESS`, `FAILURE`
+    /// or `IGNORED`, was not received yet.
+    pub fn pending_responses(&self) -> usize {
+        self.pending
+    }
+
+    /// Sets a wire tap which receives every message sent or received from now on.
+    pub fn set_wire_tap(&mut self, wire_tap: Option<Arc<dyn WireTap>>) {
+        self.wire_tap = wire_tap;
+    }
+
+    fn tap(&self, direction: Direction, bytes: &[u8]) {
+        if let Some(wire_tap) = &self.wire_tap {
+            wire_tap.frame(&Frame { direction, timestamp: SystemTime::now(), bytes });
         }
+    }
 
-        self.writer.flush().await?;
+    /// Sets the routing context which is sent with the `HELLO`.
+    pub fn set_routing_context(&mut self, routing_context: Option<HashMap<String, String>>) {
+        self.routing_context = routing_context;
+    }
+
+    /// The agent of the server as reported in the `HELLO` response, like `Neo4j/4.1.0`.
+    pub fn server_agent(&self) -> Option<&str> {
+        self.server_agent.as_deref()
+    }
+
+    /// The id the server assigned to this connection in the `HELLO` response, like `bolt-42`.
+    pub fn connection_id(&self) -> Option<&str> {
+        self.connection_id.as_deref()
+    }
+
+    /// Checks if date times with zone are encoded in UTC on this connection, which is the case
+    /// since 5.0 or if the server agreed on the `utc` patch.
+    pub fn is_utc(&self) -> bool {
+        self.utc_patched || matches!(self.version, Some(v) if v >= Version::new(5, 0))
+    }
+
+    /// The version negotiated in the handshake, if there was one.
+    pub fn version(&self) -> Option<Version> {
+        self.version
+    }
+
+    /// The time since the connection was established.
+    pub fn age(&self) -> Duration {
+        self.created.elapsed()
+    }
+
+    /// The time since the last response was received on this connection.
+    pub fn idle_time(&self) -> Duration {
+        self.last_used.elapsed()
+    }
+
+    /// Performs a handshake as specified in the bolt protocol. A successful handshake ends in a
+    /// negotiated version between the client and a server, which is kept by the connection.
+    pub async fn handshake<V: Into<SupportedVersions>>(&mut self, versions: V) -> Result<Version, ConnectionError> {
+        let versions = versions.into();
+        let (reader, writer) = (&mut self.reader, &mut self.writer);
+        let exchange = async {
+            writer.write(&[0x60, 0x60, 0xB0, 0x17]).await?;
+            for v in versions.ranges() {
+                writer.write(&v.encode()).await?;
+            }
+
+            writer.flush().await?;
+
+            // server responses with a `Version`:
+            let mut buffer = [0u8, 0, 0, 0];
+            reader.read_exact(&mut buffer).await?;
+            Ok(buffer)
+        };
+
+        let buffer = match with_timeout(self.config.handshake_timeout, exchange).await {
+            Ok(buffer) => buffer,
+            Err(e) => {
+                self.state = State::Closed;
+                return Err(e);
+            }
+        };
 
-        // server responses with a `Version`:
-        let mut buffer = [0u8, 0, 0, 0];
-        self.reader.read_exact(&mut buffer).await?;
         let version = Version::decode(&buffer);
         if version.is_empty() {
             self.state = State::Closed;
-            Err(ConnectionError::VersionsNotSupportedByServer(*versions))
+            Err(ConnectionError::VersionsNotSupportedByServer(versions))
         } else {
             self.state = State::Ready;
+            self.version = Some(version);
             Ok(version)
         }
     }
@@ -123,19 +348,81 @@ impl Connection {
     /// Sends any value which can be packed into a message, using PackStream,
     /// (c.f. [`packable`](packs::packable)). It returns the number of sent bytes.
     pub async fn send<V: Pack>(&mut self, value: &V) -> Result<usize, ConnectionError> {
-        let mut message =
-            Message::new_alloc(
-                self.config.initial_chunks,
-                self.config.chunk_capacity);
-        value.encode(&mut message)?;
-        Ok(message.pack(&mut self.writer).await?)
+        self.write_message(value).await
+    }
+
+    async fn write_message<V: Pack>(&mut self, value: &V) -> Result<usize, ConnectionError> {
+        let message = self.send_buffer();
+        value.encode(message)?;
+        let written =
+            if self.wire_tap.is_some() {
+                let mut bytes = Vec::new();
+                self.send_buffer.pack(&mut bytes).await?;
+                self.tap(Direction::Outgoing, &bytes);
+                self.writer.write_all(&bytes).await?;
+                self.writer.flush().await?;
+                bytes.len()
+            } else {
+                self.send_buffer.pack(&mut self.writer).await?
+            };
+        self.pending += 1;
+        Ok(written)
+    }
+
+    /// Starts a [`Pipeline`](crate::connectivity::pipeline::Pipeline), which sends several
+    /// requests at once.
+    pub fn pipeline(&mut self) -> Pipeline<'_, S> {
+        Pipeline::new(self)
+    }
+
+    /// The emptied message which is reused to encode requests.
+    pub(crate) fn send_buffer(&mut self) -> &mut Message {
+        self.send_buffer.clear();
+        &mut self.send_buffer
+    }
+
+    /// Writes the provided number of already packed requests and flushes.
+    pub(crate) async fn write_raw(&mut self, bytes: &[u8], requests: usize) -> Result<(), ConnectionError> {
+        self.tap(Direction::Outgoing, bytes);
+        self.writer.write_all(bytes).await?;
+        self.writer.flush().await?;
+        self.pending += requests;
+        Ok(())
     }
 
     /// Tries to receive any value which can be unpacked from a message, using PackStream. These
     /// are usually the [`responses`](crate::client::response).
     pub async fn recv<T: Unpack>(&mut self) -> Result<T, ConnectionError> {
-        let mut message = Message::unpack(&mut self.reader).await?;
-        Ok(T::decode(&mut message)?)
+        let (reader, buffer) = (&mut self.reader, &mut self.recv_buffer);
+        let max_size = self.config.max_message_size;
+        let unpack = async {
+            buffer.unpack_into(reader, max_size).await.map_err(|e| {
+                match e.get_ref().and_then(|inner| inner.downcast_ref::<MessageTooLarge>()) {
+                    Some(MessageTooLarge(max)) => ConnectionError::MessageTooLarge(*max),
+                    None => ConnectionError::IOError(e),
+                }
+            })
+        };
+
+        if let Err(e) = with_timeout(self.config.recv_timeout, unpack).await {
+            // a partially read message cannot be recovered from:
+            if let ConnectionError::Timeout | ConnectionError::MessageTooLarge(_) = e {
+                self.state = State::Closed;
+            }
+            return Err(e);
+        }
+
+        self.last_used = Instant::now();
+        if self.wire_tap.is_some() {
+            let mut bytes = Vec::new();
+            self.recv_buffer.pack(&mut bytes).await?;
+            self.tap(Direction::Incoming, &bytes);
+        }
+
+        if !self.received_record() {
+            self.pending = self.pending.saturating_sub(1);
+        }
+        Ok(T::decode(&mut self.recv_buffer)?)
     }
 
     /// Tries to receive a `SUCCESS`. Turns a `FAILURE` into a `ConnectionError` and every other
@@ -144,24 +431,109 @@ impl Connection {
         let response = self.recv::<Response>().await?;
         match response {
             Response::Success(s) => Ok(s),
-            Response::Failure(f) => Err(f.into()),
+            Response::Failure(f) => Err(self.failure(f)),
             _ => Err(ConnectionError::UnexpectedResponse),
         }
     }
 
+    /// Checks if the last received message is a `RECORD`, by its structure tag; every other
+    /// response ends a request.
+    fn received_record(&self) -> bool {
+        self.recv_buffer
+            .chunks()
+            .next()
+            .and_then(|chunk| chunk.as_bytes().get(1))
+            .map(|tag| *tag == 0x71)
+            .unwrap_or_default()
+    }
+
+    /// Turns a `FAILURE` into an error and marks the connection as failed, or closes it if the
+    /// failure is fatal to it, such that it does not get recycled.
+    pub(crate) fn failure(&mut self, f: Failure) -> ConnectionError {
+        let error = Neo4jError::from(f);
+        self.state =
+            if error.is_fatal_to_connection() { State::Closed } else { State::Failed };
+
+        ConnectionError::FailureResponse(error)
+    }
+
     /// A higher-level function which sends a `HELLO` request to authenticate the connection. Waits
-    /// for a response and reports any non `SUCCESS` as an error.
+    /// for a response and reports any non `SUCCESS` as an error. Since 5.1, the `HELLO` carries no
+    /// authentication and is followed by a `LOGON`.
     pub async fn auth_hello(&mut self, agent_name: &str, version: &str, auth_scheme: &str, auth_principal: &str, auth_credentials: &str) -> Result<Success, ConnectionError> {
-        self.send(
-            &Hello::new(agent_name, version, auth_scheme, auth_principal, auth_credentials))
-            .await?;
+        if matches!(self.version, Some(v) if v.supports_logon()) {
+            let hello = self.complete_hello(Hello::without_auth(agent_name, version));
+            self.send(&hello).await?;
+            let hello_success = self.recv_auth_success().await?;
+            self.greeted(&hello_success);
+            self.logon(auth_scheme, auth_principal, auth_credentials).await?;
+            return Ok(hello_success);
+        }
+
+        let hello =
+            self.complete_hello(
+                Hello::new(agent_name, version, auth_scheme, auth_principal, auth_credentials));
+        self.send(&hello).await?;
 
+        let hello_success = self.recv_auth_success().await?;
+        self.greeted(&hello_success);
+        Ok(hello_success)
+    }
+
+    /// Keeps the server information of the `HELLO` response.
+    fn greeted(&mut self, hello_success: &Success) {
+        self.server_agent = hello_success.metadata.get_property_typed("server").cloned();
+        self.connection_id = hello_success.metadata.get_property_typed("connection_id").cloned();
+        let patches: Option<Vec<&String>> =
+            hello_success.metadata.get_property("patch_bolt").and_then(extract_list_ref);
+        self.utc_patched = patches.unwrap_or_default().iter().any(|p| *p == "utc");
+    }
+
+    /// Completes a `HELLO` by the routing context and, if supported, the `bolt_agent` and the
+    /// `utc` patch.
+    fn complete_hello(&self, mut hello: Hello) -> Hello {
+        if self.config.utc_patch && matches!(self.version, Some(v) if v.supports_utc_patch()) {
+            hello.set_patch_bolt(&["utc"]);
+        }
+
+        if matches!(self.version, Some(v) if v.supports_bolt_agent()) {
+            hello.set_bolt_agent();
+        }
+
+        if let Some(context) = &self.routing_context {
+            let mut routing = Dictionary::with_capacity(context.len());
+            for (key, value) in context {
+                routing.add_property(key.as_str(), value.as_str());
+            }
+            hello.set_routing(routing);
+        }
+
+        hello
+    }
+
+    /// Sends a `LOGON` to authenticate an already greeted connection (since 5.1). Waits for a
+    /// response and reports any non `SUCCESS` as an error.
+    pub async fn logon(&mut self, auth_scheme: &str, auth_principal: &str, auth_credentials: &str) -> Result<Success, ConnectionError> {
+        self.send(&Logon::new(auth_scheme, auth_principal, auth_credentials)).await?;
+        self.recv_auth_success().await
+    }
+
+    /// Sends a `LOGOFF` which removes the authentication of the connection (since 5.1). A new
+    /// `LOGON` is needed afterwards.
+    pub async fn logoff(&mut self) -> Result<(), ConnectionError> {
+        self.send(&Logoff {}).await?;
+        let _ = self.recv_success().await?;
+        Ok(())
+    }
+
+    /// Receives the response to an authentication; any non `SUCCESS` closes the connection.
+    async fn recv_auth_success(&mut self) -> Result<Success, ConnectionError> {
         let response = self.recv::<Response>().await?;
         match response {
             Response::Success(s) => Ok(s),
-            Response::Failure(mut f) => {
+            Response::Failure(f) => {
                 self.state = State::Closed;
-                Err(ConnectionError::AuthenticationError(f.message().clone(), f.code().clone()))
+                Err(ConnectionError::AuthenticationError(Neo4jError::from(f).into()))
             }
 
             _ => {
@@ -195,20 +567,123 @@ impl Connection {
                     }
                 }
                 Response::Failure(f) =>
-                    return Err(f.into()),
+                    return Err(self.failure(f)),
                 Response::Ignored(_) =>
                     return Ok(StreamResult::Ignored),
             }
         }
     }
 
+    /// Pulls in batches of `n` records as long as the server reports more records, i.e. until the
+    /// stream has finished or a pull got ignored. All records are collected.
+    pub async fn pull_batched(&mut self, n: Amount, qid: Qid) -> Result<StreamResult, ConnectionError> {
+        let mut results = Vec::new();
+        loop {
+            match self.pull(n, qid).await? {
+                StreamResult::HasMore(records) =>
+                    results.extend(records),
+                StreamResult::Finished(s, records) => {
+                    results.extend(records);
+                    return Ok(StreamResult::Finished(s, results));
+                }
+                StreamResult::Ignored =>
+                    return Ok(StreamResult::Ignored),
+            }
+        }
+    }
+
+    /// Sends a `DISCARD` for the remaining records of a stream and receives its `SUCCESS`, which
+    /// ends the stream, unless only `n` records were discarded and the server has more.
+    pub async fn discard(&mut self, n: Amount, qid: Qid) -> Result<Success, ConnectionError> {
+        self.send(&Discard::new(n, qid)).await?;
+        self.recv_success().await
+    }
+
+    /// A higher-level function which sends a `ROUTE` and reads out the routing table from the
+    /// returned `SUCCESS`.
+    /// The shape of the request is adapted to the negotiated version.
+    pub async fn route(&mut self, route: Route) -> Result<RoutingTable, ConnectionError> {
+        let route = match self.version {
+            Some(version) => route.for_version(version),
+            None => route,
+        };
+        self.send(&route).await?;
+        let success = self.recv_success().await?;
+        RoutingTable::from_success(success).ok_or(ConnectionError::NoRoutingTableInformation)
+    }
+
+    /// Sends a `GOODBYE`, which the server does not answer but closes the connection; hence
+    /// the connection is closed afterwards, whether sending succeeded or not.
     pub async fn goodbye(&mut self) -> Result<(), ConnectionError> {
-        self.send(&GoodBye {}).await?;
-        Ok(())
+        let sent = self.write_message(&GoodBye {}).await;
+        self.state = State::Closed;
+        self.pending = 0;
+        sent.map(|_| ())
     }
 
+    /// Sends a `RESET` and waits for its `SUCCESS`, which brings the connection back into a
+    /// clean state. Since it needs a round trip, it also verifies the connection is still alive.
+    /// Responses to requests sent before the `RESET` which were not received yet, like `IGNORED`
+    /// or a late `SUCCESS`, are skipped.
     pub async fn reset(&mut self) -> Result<(), ConnectionError> {
-        self.send(&Reset {}).await?;
+        self.write_message(&Reset {}).await?;
+        loop {
+            let response = self.recv::<Response>().await?;
+            if self.pending > 0 {
+                continue;
+            }
+
+            match response {
+                Response::Success(_) => {
+                    self.state = State::Ready;
+                    return Ok(());
+                }
+                Response::Ignored(_) | Response::Record(_) => continue,
+                Response::Failure(f) => {
+                    self.state = State::Closed;
+                    return Err(f.into());
+                }
+            }
+        }
+    }
+
+    /// Marks the connection to be reset once it is recycled, e.g. since it was left within
+    /// a transaction. A `RESET` rolls back an open transaction.
+    pub fn mark_for_reset(&mut self) {
+        if self.state == State::Ready {
+            self.state = State::Failed;
+        }
+    }
+
+    /// Resets the connection if it has failed.
+    pub async fn recover(&mut self) -> Result<(), ConnectionError> {
+        if self.state == State::Failed {
+            if let Err(e) = self.reset().await {
+                self.state = State::Closed;
+                return Err(e);
+            }
+        }
+
         Ok(())
     }
+
+    /// Verifies the connection is alive and able to run queries by running `RETURN 1`.
+    pub async fn ping(&mut self) -> Result<(), ConnectionError> {
+        let query = Query::new("RETURN 1");
+        self.send(&Run::new(&query)).await?;
+        let _ = self.recv_success().await?;
+        match self.pull(Amount::All, Qid::Last).await? {
+            StreamResult::Finished(_, _) => Ok(()),
+            _ => Err(ConnectionError::UnexpectedResponse),
+        }
+    }
+}
+
+/// Awaits `f` for at most `duration`, if there is any.
+async fn with_timeout<T, F>(duration: Option<Duration>, f: F) -> Result<T, ConnectionError>
+    where F: Future<Output = Result<T, ConnectionError>> {
+    match duration {
+        Some(duration) => timeout(duration, f).await.map_err(|_| ConnectionError::Timeout)?,
+        None => f.await,
+    }
 }
@@ -1,65 +1,205 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::future::timeout;
 use crate::connectivity::connection::{Connection, ConnectionError, ConnectionConfig, State};
 use deadpool::managed::{RecycleResult, RecycleError};
 use async_trait::async_trait;
-use crate::connectivity::version::Version;
-use crate::client::auth::{AuthData, AuthMethod};
+use crate::client::auth::AuthProvider;
+use crate::connectivity::pool::PoolMetrics;
+use crate::client::metrics::MetricsObserver;
+use crate::connectivity::wire_tap::WireTap;
+use crate::connectivity::backoff::{BackoffConfig, CircuitBreaker};
 
 /// Handles the opening and recycling of connections.
 pub struct Manager {
     endpoint: String,
     connection_config: ConnectionConfig,
-    authentication: AuthData,
+    authentication: Arc<dyn AuthProvider>,
     agent_name: String,
     agent_version: String,
+    max_lifetime: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    metrics: Arc<PoolMetrics>,
+    observer: Option<Arc<dyn MetricsObserver>>,
+    routing_context: Option<HashMap<String, String>>,
+    wire_tap: Option<Arc<dyn WireTap>>,
+    create_timeout: Option<Duration>,
+    recycle_timeout: Option<Duration>,
+    breaker: CircuitBreaker,
 }
 
 impl Manager {
-    pub fn new<A: AuthMethod>(
+    /// Creates a manager which authenticates every new connection with a token of `auth`.
+    pub fn new(
         endpoint: String,
-        auth: A,
+        auth: Arc<dyn AuthProvider>,
         agent_name: &str,
         agent_version: &str,
         connection_config: &ConnectionConfig) -> Self {
         Manager {
             endpoint,
             connection_config: *connection_config,
-            authentication: auth.into_auth_data(),
+            authentication: auth,
             agent_version: String::from(agent_version),
             agent_name: String::from(agent_name),
+            max_lifetime: None,
+            idle_timeout: None,
+            metrics: Arc::new(PoolMetrics::default()),
+            observer: None,
+            routing_context: None,
+            wire_tap: None,
+            create_timeout: None,
+            recycle_timeout: None,
+            breaker: CircuitBreaker::new(BackoffConfig::default()),
         }
     }
+
+    /// Sets the age after which connections are closed instead of being reused.
+    pub fn max_lifetime(mut self, max_lifetime: Option<Duration>) -> Self {
+        self.max_lifetime = max_lifetime;
+        self
+    }
+
+    /// Sets the time a connection might be idle in the pool before it is closed instead of being
+    /// reused.
+    pub fn idle_timeout(mut self, idle_timeout: Option<Duration>) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Sets an observer which is notified about every newly created connection.
+    pub fn observer(mut self, observer: Option<Arc<dyn MetricsObserver>>) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    /// Sets the routing context which is sent with the `HELLO` of every new connection.
+    pub fn routing_context(mut self, routing_context: Option<HashMap<String, String>>) -> Self {
+        self.routing_context = routing_context;
+        self
+    }
+
+    /// Sets a wire tap which receives every message of every new connection.
+    pub fn wire_tap(mut self, wire_tap: Option<Arc<dyn WireTap>>) -> Self {
+        self.wire_tap = wire_tap;
+        self
+    }
+
+    /// Sets how long opening, handshaking and authenticating a new connection might take before
+    /// it fails with `Timeout`.
+    pub fn create_timeout(mut self, create_timeout: Option<Duration>) -> Self {
+        self.create_timeout = create_timeout;
+        self
+    }
+
+    /// Sets how long resetting a connection which is taken out of the pool might take before the
+    /// connection is discarded and another one is taken.
+    pub fn recycle_timeout(mut self, recycle_timeout: Option<Duration>) -> Self {
+        self.recycle_timeout = recycle_timeout;
+        self
+    }
+
+    /// Sets how creating connections backs off after the server could not be reached.
+    pub fn backoff(mut self, backoff: BackoffConfig) -> Self {
+        self.breaker = CircuitBreaker::new(backoff);
+        self
+    }
+
+    /// The counters of created, recycled and discarded connections, shared with the pool user.
+    pub fn metrics(&self) -> Arc<PoolMetrics> {
+        self.metrics.clone()
+    }
+
+    fn is_expired(&self, connection: &Connection) -> bool {
+        matches!(self.max_lifetime, Some(max) if connection.age() > max)
+            || matches!(self.idle_timeout, Some(max) if connection.idle_time() > max)
+    }
 }
 
 #[async_trait]
 impl deadpool::managed::Manager<Connection, ConnectionError> for Manager {
     async fn create(&self) -> Result<Connection, ConnectionError> {
+        self.breaker.before_attempt().await?;
+
+        let result = match self.create_timeout {
+            None => self.try_create().await,
+            Some(duration) => timeout(duration, self.try_create()).await.unwrap_or_else(|_| Err(ConnectionError::Timeout)),
+        };
+
+        // only an unreachable server counts as a failure; any answer, even a failed
+        // authentication, shows that it is reachable:
+        match &result {
+            Err(ConnectionError::IOError(_)) | Err(ConnectionError::Timeout) => self.breaker.failed(),
+            _ => self.breaker.succeeded(),
+        }
+
+        result
+    }
+
+    async fn recycle(&self, obj: &mut Connection) -> RecycleResult<ConnectionError> {
+        let result = match self.recycle_timeout {
+            None => self.try_recycle(obj).await,
+            Some(duration) => timeout(duration, self.try_recycle(obj)).await
+                .unwrap_or_else(|_| Err(RecycleError::Message(String::from("Recycling timed out.")))),
+        };
+
+        match result {
+            Ok(()) => self.metrics.connection_recycled(),
+            Err(_) => self.metrics.connection_discarded(),
+        }
+
+        result
+    }
+}
+
+impl Manager {
+    async fn try_create(&self) -> Result<Connection, ConnectionError> {
         // connect:
         let mut connection = Connection::connect(&self.endpoint, self.connection_config).await?;
 
-        // handshake with fixed supported versions:
-        let _ = connection.handshake(
-            &[
-                Version::new(4,1),
-                Version::new(4,0),
-                Version::empty(),
-                Version::empty()]).await?;
+        // handshake with the configured versions:
+        let _ = connection.handshake(self.connection_config.supported_versions()).await?;
 
-        // authenticate:
+        connection.set_routing_context(self.routing_context.clone());
+        connection.set_wire_tap(self.wire_tap.clone());
+
+        // authenticate with a fresh token:
+        let token = self.authentication.get_token().await?;
         let _ = connection
             .auth_hello(
                 &self.agent_name,
                 &self.agent_version,
-                &self.authentication.scheme,
-                &self.authentication.principal,
-                &self.authentication.credentials).await?;
+                &token.scheme,
+                &token.principal,
+                &token.credentials).await?;
+
+        self.metrics.connection_created();
+        if let Some(observer) = &self.observer {
+            observer.connection_created();
+        }
 
         Ok(connection)
     }
 
-    async fn recycle(&self, obj: &mut Connection) -> RecycleResult<ConnectionError> {
+    async fn try_recycle(&self, obj: &mut Connection) -> RecycleResult<ConnectionError> {
         match obj.state() {
-            State::Ready => {
+            State::Ready | State::Failed if self.is_expired(obj) => {
+                let _ = obj.goodbye().await;
+                Err(RecycleError::Message(String::from("Connection expired, closing it.")))
+            },
+            State::Ready | State::Failed => {
+                let idle_time = obj.idle_time();
                 obj.reset().await?;
+
+                // a connection idle for too long might have been killed silently:
+                if let Some(max_idle) = self.connection_config.connection_idle() {
+                    if idle_time > max_idle {
+                        obj.ping().await?;
+                    }
+                }
+
                 Ok(())
             },
             _ => Err(
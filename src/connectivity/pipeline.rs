@@ -0,0 +1,90 @@
+use packs::Pack;
+
+use crate::connectivity::connection::{Connection, ConnectionError};
+use crate::connectivity::stream::BoltTransport;
+use crate::messaging::response::{Record, Response, Success};
+
+/// A batch of requests which are sent at once, without waiting for any response in between. The
+/// responses are received afterwards in the order of the requests. Since bolt processes requests
+/// in order, a `RUN` and its `PULL` can be sent together, saving a round trip:
+/// ```no_run
+/// # use raio::connectivity::connection::{Connection, ConnectionConfig, ConnectionError};
+/// # use raio::messaging::request::{Run, Pull};
+/// # use raio::messaging::query::Query;
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), ConnectionError> {
+/// # let mut connection = Connection::connect("localhost:7687", ConnectionConfig::default()).await?;
+/// let query = Query::new("RETURN 1 as x");
+///
+/// let mut pipeline = connection.pipeline();
+/// pipeline.queue(&Run::new(&query)).await?;
+/// pipeline.queue(&Pull::all_from_last()).await?;
+///
+/// let responses = pipeline.execute().await?;
+/// assert_eq!(responses[1].records.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Pipeline<'c, S: BoltTransport> {
+    connection: &'c mut Connection<S>,
+    buffer: Vec<u8>,
+    queued: usize,
+}
+
+/// The responses to a single pipelined request: the `RECORD`s it produced, if any, and the
+/// response which ends it. An `IGNORED`, which follows a failed request, is reported as an
+/// `UnexpectedResponse`.
+pub struct PipelinedResponse {
+    pub records: Vec<Record>,
+    pub summary: Result<Success, ConnectionError>,
+}
+
+impl<'c, S: BoltTransport> Pipeline<'c, S> {
+    pub(crate) fn new(connection: &'c mut Connection<S>) -> Self {
+        Pipeline {
+            connection,
+            buffer: Vec::new(),
+            queued: 0,
+        }
+    }
+
+    /// Queues a request; nothing is sent yet.
+    pub async fn queue<V: Pack>(&mut self, value: &V) -> Result<(), ConnectionError> {
+        let message = self.connection.send_buffer();
+        value.encode(message)?;
+        message.pack(&mut self.buffer).await?;
+        self.queued += 1;
+        Ok(())
+    }
+
+    /// The number of queued requests.
+    pub fn len(&self) -> usize {
+        self.queued
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queued == 0
+    }
+
+    /// Sends all queued requests at once and receives a `PipelinedResponse` per request.
+    pub async fn execute(self) -> Result<Vec<PipelinedResponse>, ConnectionError> {
+        self.connection.write_raw(&self.buffer, self.queued).await?;
+
+        let mut responses = Vec::with_capacity(self.queued);
+        for _ in 0..self.queued {
+            let mut records = Vec::new();
+            let summary = loop {
+                match self.connection.recv::<Response>().await? {
+                    Response::Record(r) => records.push(r),
+                    Response::Success(s) => break Ok(s),
+                    Response::Failure(f) => break Err(self.connection.failure(f)),
+                    Response::Ignored(_) => break Err(ConnectionError::UnexpectedResponse),
+                }
+            };
+
+            responses.push(PipelinedResponse { records, summary });
+        }
+
+        Ok(responses)
+    }
+}
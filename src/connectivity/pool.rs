@@ -1,4 +1,155 @@
+use std::ops::DerefMut;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use deadpool::managed::Object;
+
+use crate::client::error::ClientError;
 use crate::connectivity::connection::{Connection, ConnectionError};
 
 /// A type alias for a managed pool of connections.
-pub type Pool = deadpool::managed::Pool<Connection, ConnectionError>;
\ No newline at end of file
+pub type Pool = deadpool::managed::Pool<Connection, ConnectionError>;
+
+/// A connection checked out of a [`ConnectionPool`], which returns it to the pool when dropped.
+pub trait ConnectionGuard: DerefMut<Target = Connection> + Send {
+    /// Takes the connection out of the pool for good, e.g. since it broke.
+    fn detach(self: Box<Self>) -> Connection;
+}
+
+impl ConnectionGuard for Object<Connection, ConnectionError> {
+    fn detach(self: Box<Self>) -> Connection {
+        Object::take(*self)
+    }
+}
+
+/// A connection checked out of any [`ConnectionPool`].
+pub type PooledConnection = Box<dyn ConnectionGuard>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// The momentary state of a [`ConnectionPool`].
+pub struct PoolState {
+    pub max_size: usize,
+    /// The number of open connections, idle or in use.
+    pub size: usize,
+    /// The number of idle connections.
+    pub available: usize,
+    /// The number of requests waiting for a connection.
+    pub waiting: usize,
+}
+
+/// A pool of connections, which the [`Client`](crate::client::Client) checks connections out
+/// of. The default is a `deadpool` [`Pool`](crate::connectivity::pool::Pool) with the
+/// [`Manager`](crate::connectivity::manager::Manager), but any pool can be plugged in with
+/// [`Client::with_pool`](crate::client::Client::with_pool). A pool is expected to hand out
+/// connections which are authenticated and in a clean state, i.e. to `RESET` connections before
+/// reusing them.
+#[async_trait]
+pub trait ConnectionPool: Send + Sync {
+    /// Checks out an idle connection or creates a new one, waiting if the pool is full.
+    async fn get(&self) -> Result<PooledConnection, ClientError>;
+
+    fn state(&self) -> PoolState;
+}
+
+#[async_trait]
+impl ConnectionPool for Pool {
+    async fn get(&self) -> Result<PooledConnection, ClientError> {
+        Ok(Box::new(Pool::get(self).await?))
+    }
+
+    fn state(&self) -> PoolState {
+        let status = self.status();
+        PoolState {
+            max_size: status.max_size,
+            size: status.size,
+            available: status.available.max(0) as usize,
+            waiting: (-status.available).max(0) as usize,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+/// Counters of a pool, which are updated by its [`Manager`](crate::connectivity::manager::Manager)
+/// and by the client checking out connections.
+pub struct PoolMetrics {
+    created: AtomicU64,
+    recycled: AtomicU64,
+    discarded: AtomicU64,
+    acquisitions: AtomicU64,
+    total_wait_micros: AtomicU64,
+    max_wait_micros: AtomicU64,
+}
+
+impl PoolMetrics {
+    pub(crate) fn connection_created(&self) {
+        self.created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn connection_recycled(&self) {
+        self.recycled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn connection_discarded(&self) {
+        self.discarded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn connection_acquired(&self, wait: Duration) {
+        let micros = wait.as_micros() as u64;
+        self.acquisitions.fetch_add(1, Ordering::Relaxed);
+        self.total_wait_micros.fetch_add(micros, Ordering::Relaxed);
+        self.max_wait_micros.fetch_max(micros, Ordering::Relaxed);
+    }
+
+    /// A snapshot of the counters together with the current state of `pool`.
+    pub fn status(&self, state: PoolState) -> PoolStatus {
+        PoolStatus {
+            max_size: state.max_size,
+            size: state.size,
+            available: state.available,
+            in_use: state.size.saturating_sub(state.available),
+            waiting: state.waiting,
+            created: self.created.load(Ordering::Relaxed),
+            recycled: self.recycled.load(Ordering::Relaxed),
+            discarded: self.discarded.load(Ordering::Relaxed),
+            acquisitions: self.acquisitions.load(Ordering::Relaxed),
+            total_wait: Duration::from_micros(self.total_wait_micros.load(Ordering::Relaxed)),
+            max_wait: Duration::from_micros(self.max_wait_micros.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// The state of a connection pool and the counters since its creation.
+pub struct PoolStatus {
+    pub max_size: usize,
+    /// The number of open connections, idle or in use.
+    pub size: usize,
+    /// The number of idle connections.
+    pub available: usize,
+    pub in_use: usize,
+    /// The number of requests waiting for a connection.
+    pub waiting: usize,
+    pub created: u64,
+    pub recycled: u64,
+    /// The number of connections closed instead of being recycled, e.g. since they failed or
+    /// expired.
+    pub discarded: u64,
+    /// The number of checked out connections.
+    pub acquisitions: u64,
+    /// The time spent waiting for connections in total.
+    pub total_wait: Duration,
+    /// The longest time spent waiting for a connection.
+    pub max_wait: Duration,
+}
+
+impl PoolStatus {
+    /// The average time spent waiting for a connection.
+    pub fn mean_wait(&self) -> Duration {
+        if self.acquisitions == 0 {
+            Duration::from_secs(0)
+        } else {
+            Duration::from_micros((self.total_wait.as_micros() / u128::from(self.acquisitions)) as u64)
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use async_std::sync::{Mutex, MutexGuard};
+use packs::Pack;
+
+use crate::connectivity::connection::{Connection, ConnectionError};
+use crate::connectivity::pipeline::PipelinedResponse;
+use crate::connectivity::stream::{BoltStream, BoltTransport};
+use crate::messaging::query::Query;
+use crate::messaging::request::{Pull, Run};
+
+/// A connection which can be used by several tasks at once. Each request is sent and its
+/// responses are received while the connection is locked, so every task receives exactly the
+/// responses to its own requests, no matter how requests of different tasks interleave:
+/// ```no_run
+/// # use raio::connectivity::connection::{Connection, ConnectionConfig, ConnectionError};
+/// # use raio::connectivity::shared::SharedConnection;
+/// # use raio::messaging::query::Query;
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), ConnectionError> {
+/// # let connection = Connection::connect("localhost:7687", ConnectionConfig::default()).await?;
+/// let shared = SharedConnection::new(connection);
+///
+/// let tasks = (0..4).map(|i| {
+///     let shared = shared.clone();
+///     async_std::task::spawn(async move {
+///         let mut query = Query::new("RETURN $i as i");
+///         query.param("i", i);
+///         shared.run(&query).await
+///     })
+/// });
+///
+/// for response in futures::future::join_all(tasks).await {
+///     assert_eq!(response?.records.len(), 1);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+/// Exchanges of several requests, like an explicit transaction, have to hold the connection
+/// via [`lock`](crate::connectivity::shared::SharedConnection::lock) for their whole duration.
+pub struct SharedConnection<S: BoltTransport = BoltStream> {
+    inner: Arc<Mutex<Connection<S>>>,
+}
+
+impl<S: BoltTransport> Clone for SharedConnection<S> {
+    fn clone(&self) -> Self {
+        SharedConnection {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<S: BoltTransport> SharedConnection<S> {
+    pub fn new(connection: Connection<S>) -> Self {
+        SharedConnection {
+            inner: Arc::new(Mutex::new(connection)),
+        }
+    }
+
+    /// Sends a request and receives its responses, i.e. its `RECORD`s and the summary.
+    pub async fn request<V: Pack>(&self, request: &V) -> Result<PipelinedResponse, ConnectionError> {
+        let mut connection = self.acquire().await?;
+        let mut pipeline = connection.pipeline();
+        pipeline.queue(request).await?;
+        pipeline
+            .execute()
+            .await?
+            .pop()
+            .ok_or(ConnectionError::UnexpectedResponse)
+    }
+
+    /// Runs a query as an auto-commit and pulls all of its records. The `RUN` and the `PULL` are
+    /// sent at once; the response carries the records and the summary of the `PULL`.
+    pub async fn run(&self, query: &Query) -> Result<PipelinedResponse, ConnectionError> {
+        let mut connection = self.acquire().await?;
+        let mut pipeline = connection.pipeline();
+        pipeline.queue(&Run::new(query)).await?;
+        pipeline.queue(&Pull::all_from_last()).await?;
+
+        let mut responses = pipeline.execute().await?.into_iter();
+        let _ = responses.next().ok_or(ConnectionError::UnexpectedResponse)?.summary?;
+        responses.next().ok_or(ConnectionError::UnexpectedResponse)
+    }
+
+    /// Locks the connection; if a former holder was cancelled before it received all of its
+    /// responses, the connection is reset first, which skips these responses.
+    async fn acquire(&self) -> Result<MutexGuard<'_, Connection<S>>, ConnectionError> {
+        let mut connection = self.inner.lock().await;
+        if connection.pending_responses() > 0 {
+            connection.reset().await?;
+        }
+
+        Ok(connection)
+    }
+
+    /// Locks the connection for exclusive use, until the guard is dropped.
+    pub async fn lock(&self) -> MutexGuard<'_, Connection<S>> {
+        self.inner.lock().await
+    }
+
+    /// Takes the connection back, if this is its last handle.
+    pub fn try_into_inner(self) -> Result<Connection<S>, Self> {
+        Arc::try_unwrap(self.inner)
+            .map(Mutex::into_inner)
+            .map_err(|inner| SharedConnection { inner })
+    }
+}
@@ -0,0 +1,140 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use async_std::io::{self, Read, Write};
+use async_std::net::{TcpStream, ToSocketAddrs};
+use async_tls::client::TlsStream;
+use async_tls::TlsConnector;
+use futures::io::{AsyncRead, AsyncWrite};
+use rustls::{Certificate, ClientConfig, RootCertStore, ServerCertVerified, ServerCertVerifier, TLSError};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// The encryption of a connection. `TlsTrustAll` encrypts but skips the validation of the server
+/// certificate, which allows for self-signed certificates (`bolt+ssc`).
+pub enum Encryption {
+    Plain,
+    Tls,
+    TlsTrustAll,
+}
+
+/// Any byte stream a [`Connection`](crate::connectivity::connection::Connection) can speak the
+/// bolt protocol over, like TCP, TLS, Unix sockets or in-memory streams for testing.
+pub trait BoltTransport: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> BoltTransport for T {}
+
+/// The default byte stream of a [`Connection`](crate::connectivity::connection::Connection),
+/// which is either a plain TCP stream or a TLS stream on top of it.
+pub enum BoltStream {
+    Plain(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl BoltStream {
+    /// Connects to the provided address `host:port` with the given encryption. The host part is
+    /// used as the domain to validate the server certificate against.
+    pub async fn connect(addr: &str, encryption: Encryption) -> io::Result<Self> {
+        BoltStream::connect_timeout(addr, encryption, None).await
+    }
+
+    /// Connects like [`connect`](crate::connectivity::stream::BoltStream::connect). The host is
+    /// resolved anew on every call and the resolved addresses, IPv4 and IPv6, are tried in order
+    /// until one accepts, such that servers whose addresses change are found again. `timeout`
+    /// applies to each address on its own and to the TLS handshake; it fails with `TimedOut`.
+    pub async fn connect_timeout(addr: &str, encryption: Encryption, timeout: Option<Duration>) -> io::Result<Self> {
+        let stream = connect_tcp(addr, timeout).await?;
+        let connector = match encryption {
+            Encryption::Plain => return Ok(BoltStream::Plain(stream)),
+            Encryption::Tls => TlsConnector::new(),
+            Encryption::TlsTrustAll => {
+                let mut config = ClientConfig::new();
+                config
+                    .dangerous()
+                    .set_certificate_verifier(Arc::new(TrustAll));
+                TlsConnector::from(config)
+            }
+        };
+
+        let tls = with_timeout(timeout, connector.connect(domain_of(addr), stream)).await?;
+        Ok(BoltStream::Tls(Box::new(tls)))
+    }
+}
+
+/// Connects to the addresses `addr` resolves to, in order; fails with the error of the last one.
+async fn connect_tcp(addr: &str, timeout: Option<Duration>) -> io::Result<TcpStream> {
+    let mut last_error =
+        io::Error::new(io::ErrorKind::AddrNotAvailable, format!("'{}' did not resolve to any address", addr));
+    for socket_addr in addr.to_socket_addrs().await? {
+        match with_timeout(timeout, TcpStream::connect(socket_addr)).await {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = e,
+        }
+    }
+
+    Err(last_error)
+}
+
+async fn with_timeout<T, F: Future<Output = io::Result<T>>>(duration: Option<Duration>, f: F) -> io::Result<T> {
+    match duration {
+        Some(duration) => io::timeout(duration, f).await,
+        None => f.await,
+    }
+}
+
+/// Strips the port and any IPv6 brackets of an address.
+fn domain_of(addr: &str) -> &str {
+    let host = match addr.rfind(':') {
+        Some(i) if !addr[i..].contains(']') => &addr[..i],
+        _ => addr,
+    };
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+struct TrustAll;
+
+impl ServerCertVerifier for TrustAll {
+    fn verify_server_cert(
+        &self,
+        _roots: &RootCertStore,
+        _presented_certs: &[Certificate],
+        _dns_name: webpki::DNSNameRef,
+        _ocsp_response: &[u8],
+    ) -> Result<ServerCertVerified, TLSError> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+impl Read for BoltStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            BoltStream::Plain(s) => Pin::new(s).poll_read(cx, buf),
+            BoltStream::Tls(s) => Pin::new(s.as_mut()).poll_read(cx, buf),
+        }
+    }
+}
+
+impl Write for BoltStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            BoltStream::Plain(s) => Pin::new(s).poll_write(cx, buf),
+            BoltStream::Tls(s) => Pin::new(s.as_mut()).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            BoltStream::Plain(s) => Pin::new(s).poll_flush(cx),
+            BoltStream::Tls(s) => Pin::new(s.as_mut()).poll_flush(cx),
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            BoltStream::Plain(s) => Pin::new(s).poll_close(cx),
+            BoltStream::Tls(s) => Pin::new(s.as_mut()).poll_close(cx),
+        }
+    }
+}
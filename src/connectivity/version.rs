@@ -51,4 +51,149 @@ impl Version {
             min: bytes[2]
         }
     }
-}
\ No newline at end of file
+}
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    /// Orders by major first, then by minor:
+    /// ```
+    /// # use raio::connectivity::version::Version;
+    /// assert!(Version::new(4, 4) < Version::new(5, 0));
+    /// assert!(Version::new(4, 1) > Version::new(4, 0));
+    /// ```
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.maj, self.min).cmp(&(other.maj, other.min))
+    }
+}
+
+impl Version {
+    /// Selecting a database with `db` is available since 4.0.
+    pub fn supports_multi_db(&self) -> bool {
+        *self >= Version::new(4, 0)
+    }
+
+    /// `ROUTE` is available since 4.3.
+    pub fn supports_route(&self) -> bool {
+        *self >= Version::new(4, 3)
+    }
+
+    /// `LOGON` and `LOGOFF` are available since 5.1; authentication is not part of `HELLO`
+    /// anymore.
+    pub fn supports_logon(&self) -> bool {
+        *self >= Version::new(5, 1)
+    }
+
+    /// The `utc` patch can be negotiated in `HELLO` on 4.3 and 4.4; since 5.0, date times are
+    /// always encoded in UTC.
+    pub fn supports_utc_patch(&self) -> bool {
+        *self >= Version::new(4, 3) && *self < Version::new(5, 0)
+    }
+
+    /// `HELLO` carries a structured `bolt_agent` since 5.3.
+    pub fn supports_bolt_agent(&self) -> bool {
+        *self >= Version::new(5, 3)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// A range of versions of the same major, from `version` down to `range` minors below it. A range
+/// of `0` is just the version itself.
+pub struct VersionRange {
+    pub version: Version,
+    pub range: u8,
+}
+
+impl VersionRange {
+    pub fn new(version: Version, range: u8) -> Self {
+        VersionRange {
+            version,
+            range,
+        }
+    }
+
+    pub fn exact(version: Version) -> Self {
+        VersionRange::new(version, 0)
+    }
+
+    pub fn empty() -> Self {
+        VersionRange::exact(Version::empty())
+    }
+
+    /// Checks if the version is within the range:
+    /// ```
+    /// # use raio::connectivity::version::{Version, VersionRange};
+    /// let range = VersionRange::new(Version::new(4, 4), 2);
+    ///
+    /// assert!(range.contains(&Version::new(4, 2)));
+    /// assert!(range.contains(&Version::new(4, 4)));
+    /// assert!(!range.contains(&Version::new(4, 1)));
+    /// ```
+    pub fn contains(&self, version: &Version) -> bool {
+        version.maj == self.version.maj
+            && version.min <= self.version.min
+            && version.min >= self.version.min.saturating_sub(self.range)
+    }
+
+    /// Encodes the range for the handshake; the range is put into the second byte:
+    /// ```
+    /// # use raio::connectivity::version::{Version, VersionRange};
+    /// assert_eq!([0, 2, 4, 4], VersionRange::new(Version::new(4, 4), 2).encode());
+    /// ```
+    pub fn encode(&self) -> [u8; 4] {
+        [0, self.range, self.version.min, self.version.maj]
+    }
+}
+
+impl From<Version> for VersionRange {
+    fn from(version: Version) -> Self {
+        VersionRange::exact(version)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// The four version ranges a client proposes in the handshake, in order of preference.
+pub struct SupportedVersions([VersionRange; 4]);
+
+impl Default for SupportedVersions {
+    /// Proposes 4.4 down to 4.2, 4.1 and 4.0. Bolt 5 is opt-in, e.g. with
+    /// `VersionRange::new(Version::new(5, 4), 4)` as first range, since its structures, like
+    /// nodes with element ids, cannot be decoded yet.
+    fn default() -> Self {
+        SupportedVersions([
+            VersionRange::new(Version::new(4, 4), 2),
+            VersionRange::exact(Version::new(4, 1)),
+            VersionRange::exact(Version::new(4, 0)),
+            VersionRange::empty(),
+        ])
+    }
+}
+
+impl SupportedVersions {
+    pub fn new(ranges: [VersionRange; 4]) -> Self {
+        SupportedVersions(ranges)
+    }
+
+    pub fn ranges(&self) -> &[VersionRange; 4] {
+        &self.0
+    }
+
+    /// Checks if the version is within any of the proposed ranges.
+    pub fn contains(&self, version: &Version) -> bool {
+        self.0.iter().any(|r| !r.version.is_empty() && r.contains(version))
+    }
+}
+
+impl From<&[Version; 4]> for SupportedVersions {
+    fn from(versions: &[Version; 4]) -> Self {
+        SupportedVersions([
+            versions[0].into(),
+            versions[1].into(),
+            versions[2].into(),
+            versions[3].into(),
+        ])
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+use std::time::SystemTime;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    Outgoing,
+    Incoming,
+}
+
+#[derive(Debug, Copy, Clone)]
+/// A packed message as sent or received, i.e. including chunk sizes and the end marker.
+pub struct Frame<'a> {
+    pub direction: Direction,
+    pub timestamp: SystemTime,
+    pub bytes: &'a [u8],
+}
+
+impl<'a> Frame<'a> {
+    /// The bytes as upper case hex, separated by spaces.
+    pub fn hex(&self) -> String {
+        self.bytes
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl<'a> fmt::Display for Frame<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arrow = match self.direction {
+            Direction::Outgoing => "C:",
+            Direction::Incoming => "S:",
+        };
+        write!(f, "{} {}", arrow, self.hex())
+    }
+}
+
+/// Receives every message a connection sends or receives, to trace the traffic when diagnosing
+/// protocol issues. Any `Fn(&Frame)` is a wire tap:
+/// ```
+/// # use std::sync::Arc;
+/// # use raio::client::ClientConfig;
+/// # use raio::connectivity::wire_tap::Frame;
+/// let config =
+///     ClientConfig::default("my-app", "0.1.0")
+///         .wire_tap(Arc::new(|frame: &Frame| eprintln!("{}", frame)));
+/// ```
+/// The handshake is not tapped. Pipelined requests are tapped as one frame, since they are
+/// sent at once.
+pub trait WireTap: Send + Sync {
+    fn frame(&self, frame: &Frame<'_>);
+}
+
+impl<F: Fn(&Frame<'_>) + Send + Sync> WireTap for F {
+    fn frame(&self, frame: &Frame<'_>) {
+        self(frame)
+    }
+}
+
+impl fmt::Debug for dyn WireTap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WireTap")
+    }
+}
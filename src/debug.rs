@@ -0,0 +1,263 @@
+//! Renders packed bolt messages as human-readable text, for tests and for tracing the traffic of
+//! a connection, e.g. together with a [`WireTap`](crate::connectivity::wire_tap::WireTap).
+use std::convert::TryInto;
+use std::fmt::Write;
+
+/// Decodes packed bolt messages, i.e. chunks including their sizes and the end marker, into a
+/// line per message, which lists the chunk sizes and the message with its signature name and
+/// fields:
+/// ```
+/// # use raio::debug::decode_message;
+/// let bytes = [
+///     0x00, 0x0D, // chunk size
+///     0xB3, 0x10, // struct of 3 fields, RUN
+///     0x88, b'R', b'E', b'T', b'U', b'R', b'N', b' ', b'1',
+///     0xA0, 0xA0, // empty parameters and extra
+///     0x00, 0x00, // end of message
+/// ];
+///
+/// assert_eq!(decode_message(&bytes), "chunks [13]: RUN(\"RETURN 1\", {}, {})\n");
+/// ```
+/// Decoding does not stop at malformed data, which is rendered as `!!` followed by the problem
+/// and the remaining bytes in hex.
+pub fn decode_message(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        let (sizes, payload, next) = match dechunk(rest) {
+            Ok(dechunked) => dechunked,
+            Err(problem) => {
+                let _ = writeln!(out, "!! {}: {}", problem, hex(rest));
+                break;
+            }
+        };
+
+        let _ = write!(out, "chunks {:?}: ", sizes);
+        let mut decoder = Decoder { bytes: &payload, pos: 0, out: &mut out };
+        let decoded = decoder.value(0);
+        let pos = decoder.pos;
+        match decoded {
+            Err(problem) =>
+                { let _ = write!(out, " !! {}: {}", problem, hex(&payload[pos.min(payload.len())..])); }
+            Ok(()) if pos < payload.len() =>
+                { let _ = write!(out, " !! trailing bytes: {}", hex(&payload[pos..])); }
+            Ok(()) => {}
+        }
+
+        out.push('\n');
+        rest = next;
+    }
+
+    out
+}
+
+/// The chunk sizes and the content of a message, and the bytes after it.
+type Dechunked<'a> = (Vec<usize>, Vec<u8>, &'a [u8]);
+
+/// Splits off the first message.
+fn dechunk(bytes: &[u8]) -> Result<Dechunked<'_>, &'static str> {
+    let mut sizes = Vec::new();
+    let mut payload = Vec::new();
+    let mut rest = bytes;
+    loop {
+        if rest.len() < 2 {
+            return Err("message does not end");
+        }
+
+        let size = u16::from_be_bytes([rest[0], rest[1]]) as usize;
+        rest = &rest[2..];
+        if size == 0 {
+            return Ok((sizes, payload, rest));
+        }
+
+        if rest.len() < size {
+            return Err("chunk exceeds data");
+        }
+
+        sizes.push(size);
+        payload.extend_from_slice(&rest[..size]);
+        rest = &rest[size..];
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")
+}
+
+/// The name of a message, i.e. of a top-level structure.
+fn message_name(tag: u8) -> Option<&'static str> {
+    Some(match tag {
+        0x01 => "HELLO",
+        0x02 => "GOODBYE",
+        0x0F => "RESET",
+        0x10 => "RUN",
+        0x11 => "BEGIN",
+        0x12 => "COMMIT",
+        0x13 => "ROLLBACK",
+        0x2F => "DISCARD",
+        0x3F => "PULL",
+        0x54 => "TELEMETRY",
+        0x66 => "ROUTE",
+        0x6A => "LOGON",
+        0x6B => "LOGOFF",
+        0x70 => "SUCCESS",
+        0x71 => "RECORD",
+        0x7E => "IGNORED",
+        0x7F => "FAILURE",
+        _ => return None,
+    })
+}
+
+/// The name of a structure within a message.
+fn structure_name(tag: u8) -> Option<&'static str> {
+    Some(match tag {
+        0x4E => "Node",
+        0x52 => "Relationship",
+        0x72 => "UnboundRelationship",
+        0x50 => "Path",
+        0x44 => "Date",
+        0x54 => "Time",
+        0x74 => "LocalTime",
+        0x46 => "DateTime",
+        0x49 => "DateTimeUtc",
+        0x66 => "DateTimeZoneId",
+        0x69 => "DateTimeZoneIdUtc",
+        0x64 => "LocalDateTime",
+        0x45 => "Duration",
+        0x58 => "Point2D",
+        0x59 => "Point3D",
+        _ => return None,
+    })
+}
+
+struct Decoder<'a, 'o> {
+    bytes: &'a [u8],
+    pos: usize,
+    out: &'o mut String,
+}
+
+impl<'a, 'o> Decoder<'a, 'o> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], &'static str> {
+        let bytes = self.bytes.get(self.pos..self.pos + n).ok_or("unexpected end of data")?;
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    fn size(&mut self, width: usize) -> Result<usize, &'static str> {
+        let bytes = self.take(width)?;
+        Ok(match width {
+            1 => bytes[0] as usize,
+            2 => u16::from_be_bytes([bytes[0], bytes[1]]) as usize,
+            _ => u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize,
+        })
+    }
+
+    fn int(&mut self, width: usize) -> Result<i64, &'static str> {
+        let bytes = self.take(width)?;
+        Ok(match width {
+            1 => i64::from(bytes[0] as i8),
+            2 => i64::from(i16::from_be_bytes([bytes[0], bytes[1]])),
+            4 => i64::from(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+            _ => i64::from_be_bytes(bytes.try_into().map_err(|_| "invalid integer")?),
+        })
+    }
+
+    fn string(&mut self, size: usize) -> Result<(), &'static str> {
+        let bytes = self.take(size)?;
+        let _ = write!(self.out, "{:?}", String::from_utf8_lossy(bytes));
+        Ok(())
+    }
+
+    fn bytes(&mut self, size: usize) -> Result<(), &'static str> {
+        let bytes = self.take(size)?;
+        let _ = write!(self.out, "bytes[{}]", hex(bytes));
+        Ok(())
+    }
+
+    fn list(&mut self, size: usize, depth: usize) -> Result<(), &'static str> {
+        self.out.push('[');
+        self.values(size, depth)?;
+        self.out.push(']');
+        Ok(())
+    }
+
+    fn map(&mut self, size: usize, depth: usize) -> Result<(), &'static str> {
+        self.out.push('{');
+        for i in 0..size {
+            if i > 0 {
+                self.out.push_str(", ");
+            }
+            self.value(depth + 1)?;
+            self.out.push_str(": ");
+            self.value(depth + 1)?;
+        }
+        self.out.push('}');
+        Ok(())
+    }
+
+    fn structure(&mut self, size: usize, depth: usize) -> Result<(), &'static str> {
+        let tag = self.take(1)?[0];
+        let name = if depth == 0 { message_name(tag) } else { structure_name(tag) };
+        match name {
+            Some(name) => self.out.push_str(name),
+            None => { let _ = write!(self.out, "Structure<0x{:02X}>", tag); }
+        }
+
+        self.out.push('(');
+        self.values(size, depth)?;
+        self.out.push(')');
+        Ok(())
+    }
+
+    fn values(&mut self, size: usize, depth: usize) -> Result<(), &'static str> {
+        for i in 0..size {
+            if i > 0 {
+                self.out.push_str(", ");
+            }
+            self.value(depth + 1)?;
+        }
+        Ok(())
+    }
+
+    fn value(&mut self, depth: usize) -> Result<(), &'static str> {
+        let marker = self.take(1)?[0];
+        match marker {
+            0x00..=0x7F => { let _ = write!(self.out, "{}", marker); }
+            0xF0..=0xFF => { let _ = write!(self.out, "{}", marker as i8); }
+            0x80..=0x8F => self.string((marker & 0x0F) as usize)?,
+            0x90..=0x9F => self.list((marker & 0x0F) as usize, depth)?,
+            0xA0..=0xAF => self.map((marker & 0x0F) as usize, depth)?,
+            0xB0..=0xBF => self.structure((marker & 0x0F) as usize, depth)?,
+            0xC0 => self.out.push_str("null"),
+            0xC1 => {
+                let bytes = self.take(8)?;
+                let float = f64::from_be_bytes(bytes.try_into().map_err(|_| "invalid float")?);
+                let _ = write!(self.out, "{:?}", float);
+            }
+            0xC2 => self.out.push_str("false"),
+            0xC3 => self.out.push_str("true"),
+            0xC8 => { let i = self.int(1)?; let _ = write!(self.out, "{}", i); }
+            0xC9 => { let i = self.int(2)?; let _ = write!(self.out, "{}", i); }
+            0xCA => { let i = self.int(4)?; let _ = write!(self.out, "{}", i); }
+            0xCB => { let i = self.int(8)?; let _ = write!(self.out, "{}", i); }
+            0xCC => { let n = self.size(1)?; self.bytes(n)?; }
+            0xCD => { let n = self.size(2)?; self.bytes(n)?; }
+            0xCE => { let n = self.size(4)?; self.bytes(n)?; }
+            0xD0 => { let n = self.size(1)?; self.string(n)?; }
+            0xD1 => { let n = self.size(2)?; self.string(n)?; }
+            0xD2 => { let n = self.size(4)?; self.string(n)?; }
+            0xD4 => { let n = self.size(1)?; self.list(n, depth)?; }
+            0xD5 => { let n = self.size(2)?; self.list(n, depth)?; }
+            0xD6 => { let n = self.size(4)?; self.list(n, depth)?; }
+            0xD8 => { let n = self.size(1)?; self.map(n, depth)?; }
+            0xD9 => { let n = self.size(2)?; self.map(n, depth)?; }
+            0xDA => { let n = self.size(4)?; self.map(n, depth)?; }
+            _ => {
+                self.pos -= 1;
+                return Err("unknown marker");
+            }
+        }
+
+        Ok(())
+    }
+}
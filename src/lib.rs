@@ -1,3 +1,10 @@
 pub mod connectivity;
 pub mod client;
 pub mod messaging;
+pub mod debug;
+pub mod util;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "test-util")]
+pub mod testing;
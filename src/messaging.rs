@@ -1,7 +1,11 @@
 pub mod message;
 pub mod chunk;
+pub mod chunked_reader;
 pub mod request;
 pub mod response;
 pub mod bookmark;
 pub mod commit_prepare;
-pub mod query;
\ No newline at end of file
+pub mod query;
+pub mod routing_table;
+#[cfg(feature = "serde")]
+pub mod serialization;
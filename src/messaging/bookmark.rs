@@ -1,12 +1,13 @@
 use crate::messaging::response::Success;
-use crate::client::error::ClientError;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Bookmark(String);
 
 impl Bookmark {
-    pub fn from_success(s: Success) -> Result<Self, ClientError> {
-        s.into_raw_bookmark().ok_or(ClientError::NoBookmarkInformationInCommit).map(Bookmark)
+    /// The bookmark of a `SUCCESS`, if the server sent one; it might not, e.g. for read-only
+    /// queries on some setups.
+    pub fn from_success(s: Success) -> Option<Self> {
+        s.into_raw_bookmark().map(Bookmark)
     }
 
     pub fn value(&self) -> &String {
@@ -29,6 +29,11 @@ impl Chunk {
         self.written
     }
 
+    /// The bytes written into the chunk.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.written]
+    }
+
     pub fn eof(&self) -> bool {
         self.written <= self.read_cursor
     }
@@ -129,6 +134,22 @@ impl Chunk {
         self.read_cursor = new_cursor;
     }
 
+    /// Empties the chunk but keeps its allocation, such that it can be written again:
+    /// ```
+    /// # use raio::messaging::chunk::Chunk;
+    /// let mut chunk = Chunk::new(3);
+    /// chunk.write(&[1, 2, 3]);
+    ///
+    /// chunk.clear();
+    /// assert_eq!(chunk.written(), 0);
+    /// assert_eq!(chunk.write(&[4, 5, 6]), None);
+    /// ```
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.written = 0;
+        self.read_cursor = 0;
+    }
+
     /// Writes a chunk as part of a bolt message, i.e. adds the size of the chunk at the beginning.
     /// This function is an asynchronous function.
     /// ```
@@ -179,20 +200,26 @@ impl Chunk {
     /// # }
     /// ```
     pub async fn unpack<T: async_std::io::Read + Unpin>(reader: &mut T) -> async_std::io::Result<Self> {
+        let mut chunk = Chunk::new(0);
+        chunk.unpack_into(reader).await?;
+        Ok(chunk)
+    }
+
+    /// Unpacks like [`unpack`](crate::messaging::chunk::Chunk::unpack) but into this chunk,
+    /// reusing its allocation. Returns the size of the unpacked chunk.
+    pub async fn unpack_into<T: async_std::io::Read + Unpin>(&mut self, reader: &mut T) -> async_std::io::Result<usize> {
         let mut buf_size = [0u8, 0u8];
-        reader.read(&mut buf_size).await?;
+        reader.read_exact(&mut buf_size).await?;
         let size = u16::from_be_bytes(buf_size) as usize;
 
-        let mut buf = vec![0; size];
-        reader.read_exact(&mut buf).await?;
-        Ok(
-            Chunk {
-                read_cursor: 0,
-                bytes: buf,
-                capacity: size,
-                written: size,
-            }
-        )
+        self.bytes.clear();
+        self.bytes.resize(size, 0);
+        reader.read_exact(&mut self.bytes).await?;
+
+        self.capacity = size;
+        self.written = size;
+        self.read_cursor = 0;
+        Ok(size)
     }
 }
 
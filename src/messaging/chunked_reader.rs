@@ -0,0 +1,156 @@
+use std::cmp::min;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::io::AsyncRead;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum ReadState {
+    Header { buf: [u8; 2], read: usize },
+    Body { remaining: usize },
+    Done,
+}
+
+impl ReadState {
+    fn header() -> Self {
+        ReadState::Header { buf: [0, 0], read: 0 }
+    }
+
+    /// Accounts `n` freshly read header bytes; a complete header either starts a chunk body or
+    /// ends the message.
+    fn header_read(&mut self, n: usize) {
+        if let ReadState::Header { buf, read } = self {
+            *read += n;
+            if *read == 2 {
+                *self = match u16::from_be_bytes(*buf) as usize {
+                    0 => ReadState::Done,
+                    remaining => ReadState::Body { remaining },
+                };
+            }
+        }
+    }
+
+    fn body_read(&mut self, n: usize) {
+        if let ReadState::Body { remaining } = self {
+            *remaining -= n;
+            if *remaining == 0 {
+                *self = ReadState::header();
+            }
+        }
+    }
+}
+
+fn unexpected_eof() -> io::Error {
+    io::Error::new(io::ErrorKind::UnexpectedEof, "Stream ended within a message")
+}
+
+/// Reads the content of a single bolt message directly from a stream, stripping the chunk sizes
+/// on the fly. In contrast to [`Message::unpack`](crate::messaging::message::Message::unpack),
+/// the message is never held in memory as a whole, hence decoding can start as soon as the first
+/// bytes arrived:
+/// ```
+/// # use raio::messaging::chunked_reader::ChunkedReader;
+/// # use std::io::Read;
+/// let stream: &[u8] = &[0x00, 0x03, 1, 2, 3, 0x00, 0x02, 4, 5, 0x00, 0x00, 0xFF];
+/// let mut reader = ChunkedReader::new(stream);
+///
+/// let mut content = Vec::new();
+/// reader.read_to_end(&mut content).unwrap();
+///
+/// assert_eq!(content, &[1, 2, 3, 4, 5]);
+/// assert!(reader.is_done());
+/// ```
+/// The reader implements [`Read`](std::io::Read) for blocking streams and in-memory buffers, on
+/// which `packs` decodes directly, and [`AsyncRead`](futures::io::AsyncRead) for asynchronous
+/// streams. Reading ends with the empty chunk which ends the message; nothing after it is read.
+pub struct ChunkedReader<R> {
+    inner: R,
+    state: ReadState,
+}
+
+impl<R> ChunkedReader<R> {
+    pub fn new(inner: R) -> Self {
+        ChunkedReader {
+            inner,
+            state: ReadState::header(),
+        }
+    }
+
+    /// Checks if the whole message was read.
+    pub fn is_done(&self) -> bool {
+        self.state == ReadState::Done
+    }
+
+    /// Starts reading the next message from the same stream.
+    pub fn next_message(&mut self) {
+        self.state = ReadState::header();
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: io::Read> io::Read for ChunkedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match &mut self.state {
+                ReadState::Done => return Ok(0),
+                ReadState::Header { buf: header, read } => {
+                    let n = self.inner.read(&mut header[*read..])?;
+                    if n == 0 {
+                        return Err(unexpected_eof());
+                    }
+                    self.state.header_read(n);
+                }
+                ReadState::Body { remaining } => {
+                    if buf.is_empty() {
+                        return Ok(0);
+                    }
+
+                    let max = min(buf.len(), *remaining);
+                    let n = self.inner.read(&mut buf[..max])?;
+                    if n == 0 {
+                        return Err(unexpected_eof());
+                    }
+                    self.state.body_read(n);
+                    return Ok(n);
+                }
+            }
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for ChunkedReader<R> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                ReadState::Done => return Poll::Ready(Ok(0)),
+                ReadState::Header { buf: header, read } => {
+                    let n = match Pin::new(&mut this.inner).poll_read(cx, &mut header[*read..]) {
+                        Poll::Ready(Ok(0)) => return Poll::Ready(Err(unexpected_eof())),
+                        Poll::Ready(Ok(n)) => n,
+                        other => return other,
+                    };
+                    this.state.header_read(n);
+                }
+                ReadState::Body { remaining } => {
+                    if buf.is_empty() {
+                        return Poll::Ready(Ok(0));
+                    }
+
+                    let max = min(buf.len(), *remaining);
+                    let n = match Pin::new(&mut this.inner).poll_read(cx, &mut buf[..max]) {
+                        Poll::Ready(Ok(0)) => return Poll::Ready(Err(unexpected_eof())),
+                        Poll::Ready(Ok(n)) => n,
+                        other => return other,
+                    };
+                    this.state.body_read(n);
+                    return Poll::Ready(Ok(n));
+                }
+            }
+        }
+    }
+}
@@ -29,6 +29,7 @@ use std::io::Write;
 /// assert!(!dict.has_property("tx_metadata"));
 /// assert!(!dict.has_property("db"));
 /// assert!(!dict.has_property("bookmarks"));
+/// assert!(!dict.has_property("imp_user"));
 /// ```
 pub struct CommitPrepare {
    pub bookmarks: Vec<String>,
@@ -36,6 +37,7 @@ pub struct CommitPrepare {
    pub tx_metadata: Dictionary<StdStruct>,
    pub mode: Option<CommitMode>,
    pub db: Option<String>,
+   pub imp_user: Option<String>,
 }
 
 impl CommitPrepare {
@@ -46,6 +48,7 @@ impl CommitPrepare {
          tx_metadata: Dictionary::new(),
          mode: None,
          db: None,
+         imp_user: None,
       }
    }
 
@@ -64,6 +67,13 @@ impl CommitPrepare {
       self
    }
 
+   /// Runs the auto-commit or transaction as the provided user (since 4.4), which needs the
+   /// authenticated user to be allowed to impersonate.
+   pub fn set_impersonated_user(&mut self, user: Option<&str>) -> &mut Self {
+      self.imp_user = user.map(String::from);
+      self
+   }
+
    pub fn metadata(&mut self) -> &mut Dictionary<StdStruct> {
       &mut self.tx_metadata
    }
@@ -81,7 +91,8 @@ impl Pack for CommitPrepare {
              if self.tx_timeout.is_some() { 1 } else { 0 } +
              if self.tx_metadata.len() > 0 { 1 } else { 0 } +
              if self.mode.is_some() { 1 } else { 0 } +
-             if self.db.is_some() { 1 } else { 0 };
+             if self.db.is_some() { 1 } else { 0 } +
+             if self.imp_user.is_some() { 1 } else { 0 };
       let mut written = Marker::TinyDictionary(fields).encode(writer)?;
 
       if self.bookmarks.len() > 0 {
@@ -104,6 +115,10 @@ impl Pack for CommitPrepare {
          written += encode_property("db", db, writer)?;
       }
 
+      if let Some(imp_user) = &self.imp_user {
+         written += encode_property("imp_user", imp_user, writer)?;
+      }
+
       Ok(written)
    }
 }
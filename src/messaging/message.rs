@@ -1,7 +1,19 @@
 use crate::messaging::chunk::Chunk;
 use std::slice::Iter;
 use std::fmt::Formatter;
+use std::io::IoSlice;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 use async_std::prelude::*;
+use futures::io::{AsyncRead, AsyncWrite};
+use thiserror::Error;
+
+#[derive(Debug, Copy, Clone, PartialEq, Error)]
+#[error("Message exceeds the maximum size of {0} bytes")]
+/// The error within the `io::Error` returned by
+/// [`unpack_into`](crate::messaging::message::Message::unpack_into) if a message exceeds the
+/// maximum size.
+pub struct MessageTooLarge(pub usize);
 
 #[derive(Debug, Clone, PartialEq)]
 /// A `Message` is an array of bytes used to send and receive via the bolt protocol. Outside of
@@ -90,6 +102,7 @@ use async_std::prelude::*;
 /// lot of syscalls might take place.
 pub struct Message {
     chunk_capacity: u16,
+    grow_chunks: bool,
     chunks: Vec<Chunk>,
     read_cursor: usize, // index to chunk
     write_cursor: usize, // index to chunk
@@ -107,14 +120,39 @@ impl Message {
 
         Message {
             chunk_capacity,
+            grow_chunks: false,
             chunks,
             read_cursor: 0,
             write_cursor: 0,
         }
     }
 
+    /// Lets every new chunk have twice the capacity of the chunk before, up to the maximal chunk
+    /// size of 65535 bytes. Small messages still fit into the first chunk, while large messages
+    /// need fewer chunks and hence fewer size headers and writes:
+    /// ```
+    /// # use raio::messaging::message::Message;
+    /// # use std::io::Write;
+    /// let mut message = Message::new_alloc(0, 1000).grow_chunks(true);
+    /// message.write(&vec![0u8; 150_000]).unwrap();
+    ///
+    /// let capacities: Vec<usize> = message.chunks().map(|c| c.capacity()).collect();
+    /// assert_eq!(capacities, vec![1000, 2000, 4000, 8000, 16000, 32000, 64000, 65535]);
+    /// ```
+    pub fn grow_chunks(mut self, grow: bool) -> Self {
+        self.grow_chunks = grow;
+        self
+    }
+
     fn new_chunk(&mut self) -> &mut Chunk {
-        self.chunks.push(Chunk::new(self.chunk_capacity));
+        let capacity =
+            match self.chunks.last() {
+                Some(last) if self.grow_chunks =>
+                    (last.capacity() * 2).min(u16::MAX as usize) as u16,
+                _ => self.chunk_capacity,
+            };
+
+        self.chunks.push(Chunk::new(capacity));
         self.chunks.last_mut().unwrap()
     }
 
@@ -158,6 +196,66 @@ impl Message {
         self.new_chunk()
     }
 
+    /// Empties the message but keeps its chunks and their allocations, such that a message can be
+    /// reused for the next request:
+    /// ```
+    /// # use raio::messaging::message::Message;
+    /// # use std::io::Write;
+    /// let mut message = Message::new_alloc(1, 4);
+    /// message.write(&[1, 2, 3, 4, 5]).unwrap();
+    /// assert_eq!(message.chunks().len(), 2);
+    ///
+    /// message.clear();
+    /// message.write(&[6, 7]).unwrap();
+    /// assert_eq!(message.chunks().len(), 2);
+    /// assert_eq!(message.chunks().map(|c| c.written()).sum::<usize>(), 2);
+    /// ```
+    pub fn clear(&mut self) {
+        self.chunks.iter_mut().for_each(Chunk::clear);
+        self.read_cursor = 0;
+        self.write_cursor = 0;
+    }
+
+    /// The number of payload bytes in the message, i.e. without chunk sizes and the end marker:
+    /// ```
+    /// # use raio::messaging::message::Message;
+    /// # use std::io::Write;
+    /// let mut message = Message::new_alloc(0, 4);
+    /// assert!(message.is_empty());
+    ///
+    /// message.write(&[1, 2, 3, 4, 5]).unwrap();
+    /// assert_eq!(message.len(), 5);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(Chunk::written).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.iter().all(|c| c.written() == 0)
+    }
+
+    /// Allocates chunks until at least `bytes` more bytes can be written without allocating:
+    /// ```
+    /// # use raio::messaging::message::Message;
+    /// let mut message = Message::new_alloc(0, 4);
+    /// message.reserve(10);
+    ///
+    /// assert_eq!(message.chunks().len(), 3);
+    /// assert!(message.is_empty());
+    /// ```
+    pub fn reserve(&mut self, bytes: usize) {
+        let mut free: usize =
+            self.chunks
+                .iter()
+                .skip(self.write_cursor)
+                .map(|c| c.capacity() - c.written())
+                .sum();
+
+        while free < bytes {
+            free += self.new_chunk().capacity();
+        }
+    }
+
     /// Gives an iterator over the chunks of a message.
     pub fn chunks(&self) -> Iter<Chunk> {
         self.chunks.iter()
@@ -181,17 +279,79 @@ impl Message {
     /// # Ok(())
     /// # }
     /// ```
-    /// The message ends with a chunk of empty size, i.e. `0 : u16` encoded.
+    /// The message ends with a chunk of empty size, i.e. `0 : u16` encoded. All sizes and chunks
+    /// are written with a single vectored write, followed by a single flush.
     pub async fn pack<T: async_std::io::Write + Unpin>(&self, writer: &mut T) -> async_std::io::Result<usize> {
-        let mut written = 0;
-        for chunk in &self.chunks {
-            written += chunk.pack(writer).await?;
-            writer.flush().await?;
+        // empty chunks, e.g. left over from a cleared message, would end the message early:
+        let chunks: Vec<&Chunk> = self.chunks.iter().filter(|c| c.written() > 0).collect();
+        let sizes: Vec<[u8; 2]> = chunks.iter().map(|c| (c.written() as u16).to_be_bytes()).collect();
+
+        let mut slices = Vec::with_capacity(2 * chunks.len() + 1);
+        for (size, chunk) in sizes.iter().zip(chunks.iter()) {
+            slices.push(IoSlice::new(size));
+            slices.push(IoSlice::new(chunk.as_bytes()));
+        }
+        slices.push(IoSlice::new(&[0u8, 0u8]));
+        let total = slices.iter().map(|s| s.len()).sum();
+
+        // a partial write is continued slice by slice:
+        let mut written = writer.write_vectored(&slices).await?;
+        for slice in slices.iter() {
+            if written >= slice.len() {
+                written -= slice.len();
+            } else {
+                writer.write_all(&slice[written..]).await?;
+                written = 0;
+            }
         }
 
-        writer.write(&[0u8, 0u8]).await?;
         writer.flush().await?;
-        Ok(2 + written)
+        Ok(total)
+    }
+
+    /// Unpacks like [`unpack`](crate::messaging::message::Message::unpack) but into this
+    /// message, reusing its chunks and their allocations. Chunks which are left over from a
+    /// larger message before are kept empty. Fails with an `InvalidData` error containing
+    /// [`MessageTooLarge`](crate::messaging::message::MessageTooLarge) as soon as the message
+    /// exceeds `max_size` bytes; the rest of the message is not read then:
+    /// ```
+    /// # use raio::messaging::message::{Message, MessageTooLarge};
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let mut stream: &[u8] = &[0x00, 0x03, 1, 2, 3, 0x00, 0x02, 4, 5, 0x00, 0x00];
+    /// let mut message = Message::new_alloc(0, 3);
+    ///
+    /// let error = message.unpack_into(&mut stream, Some(4)).await.unwrap_err();
+    /// let too_large = error.get_ref().and_then(|e| e.downcast_ref::<MessageTooLarge>());
+    /// assert_eq!(too_large, Some(&MessageTooLarge(4)));
+    /// # }
+    /// ```
+    pub async fn unpack_into<T: async_std::io::Read + Unpin>(&mut self, reader: &mut T, max_size: Option<usize>) -> async_std::io::Result<()> {
+        self.clear();
+
+        let mut used = 0;
+        let mut size = 0;
+        loop {
+            if used == self.chunks.len() {
+                self.chunks.push(Chunk::new(0));
+            }
+
+            match self.chunks[used].unpack_into(reader).await? {
+                0 => return Ok(()),
+                n => size += n,
+            }
+
+            if let Some(max_size) = max_size {
+                if size > max_size {
+                    return Err(
+                        async_std::io::Error::new(
+                            async_std::io::ErrorKind::InvalidData,
+                            MessageTooLarge(max_size)));
+                }
+            }
+
+            used += 1;
+        }
     }
 
     /// Unpacks from a `Read` into a message. Reads in the chunks as given by the reader. The set
@@ -209,6 +369,7 @@ impl Message {
             write_cursor: 0,
             read_cursor: 0,
             chunk_capacity: first_cap as u16,
+            grow_chunks: false,
             chunks,
         })
     }
@@ -250,6 +411,44 @@ impl std::io::Read for Message {
     }
 }
 
+/// Reads like [`Read`](std::io::Read); since a message is held in memory, reading never blocks.
+/// Allows to use a message in asynchronous combinators, e.g. to copy its content into a stream:
+/// ```
+/// # use raio::messaging::message::Message;
+/// # use std::io::Write;
+/// # #[async_std::main]
+/// # async fn main() -> std::io::Result<()> {
+/// let mut message = Message::new_alloc(1, 2);
+/// Write::write_all(&mut message, &[1, 2, 3]).unwrap();
+///
+/// let mut target = Vec::new();
+/// futures::io::copy(&mut message, &mut target).await?;
+/// assert_eq!(target, &[1, 2, 3]);
+/// # Ok(())
+/// # }
+/// ```
+impl AsyncRead for Message {
+    fn poll_read(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(std::io::Read::read(self.get_mut(), buf))
+    }
+}
+
+/// Writes like [`Write`](std::io::Write); since a message is held in memory, writing never
+/// blocks.
+impl AsyncWrite for Message {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(std::io::Write::write(self.get_mut(), buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
 impl std::fmt::Display for Message {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for c in &self.chunks {
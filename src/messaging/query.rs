@@ -1,11 +1,34 @@
 use packs::{Dictionary, Value, EncodeError, Pack};
 use packs::std_structs::StdStruct;
+use std::collections::{BTreeMap, HashMap};
+use std::iter::FromIterator;
 use std::io::Write;
+use std::sync::Arc;
+use thiserror::Error;
+use crate::client::convert::{FromValue, ValueError};
+use crate::client::record_result::ConversionError;
+#[cfg(feature = "serde")]
+use crate::messaging::serialization::{SerdeError, to_value, to_dictionary};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum QueryError {
+    #[error("Parameter '${0}' is used in the query but not bound")]
+    UnboundParameter(String),
+}
+
+#[derive(Debug, Clone)]
 pub struct Query {
     str: String,
-    parameters: Dictionary<StdStruct>
+    parameters: Dictionary<StdStruct>,
+    // the statement, already encoded, if the query is prepared. It must always be the encoding
+    // of `str`; `PartialEq` compares `str` only and relies on this:
+    encoded: Option<Arc<[u8]>>,
+}
+
+impl PartialEq for Query {
+    fn eq(&self, other: &Self) -> bool {
+        self.str == other.str && self.parameters == other.parameters
+    }
 }
 
 impl Query {
@@ -13,18 +36,397 @@ impl Query {
         (self.str, self.parameters)
     }
 
+    /// The query text.
+    pub fn statement(&self) -> &str {
+        &self.str
+    }
+
+    pub fn parameters(&self) -> &Dictionary<StdStruct> {
+        &self.parameters
+    }
+
     pub fn new(query: &str) -> Query {
         Query {
             str: String::from(query),
             parameters: Dictionary::new(),
+            encoded: None,
+        }
+    }
+
+    /// Creates a prepared query, whose statement is encoded once. Use
+    /// [`bind`](crate::messaging::query::Query::bind) to get a copy for each execution, which
+    /// shares the encoded statement:
+    /// ```
+    /// # use raio::messaging::query::Query;
+    /// let prepared = Query::prepare("MATCH (p:Person {name: $name}) RETURN p");
+    ///
+    /// for name in &["Jane", "John"] {
+    ///     let mut query = prepared.bind();
+    ///     query.param("name", *name);
+    ///     assert!(query.is_prepared());
+    /// }
+    /// ```
+    /// See also [`Client::prepare`](crate::client::Client::prepare), which caches prepared
+    /// queries by their statement.
+    pub fn prepare(query: &str) -> Query {
+        let str = String::from(query);
+        let mut encoded = Vec::with_capacity(str.len() + 5);
+        let encoded = str.encode(&mut encoded).ok().map(|_| Arc::from(encoded));
+        Query {
+            str,
+            parameters: Dictionary::new(),
+            encoded,
+        }
+    }
+
+    /// A copy of the query without parameters, which shares the encoded statement if the query
+    /// is prepared.
+    pub fn bind(&self) -> Query {
+        Query {
+            str: self.str.clone(),
+            parameters: Dictionary::new(),
+            encoded: self.encoded.clone(),
+        }
+    }
+
+    /// Checks if the statement is already encoded.
+    pub fn is_prepared(&self) -> bool {
+        self.encoded.is_some()
+    }
+
+    /// Starts a `QueryBuilder`, which checks on `build` that every parameter used in the query
+    /// is bound:
+    /// ```
+    /// # use raio::messaging::query::{Query, QueryError};
+    /// let query =
+    ///     Query::builder("MATCH (p:Person {name: $name}) WHERE p.age > $age RETURN p")
+    ///         .param("name", "Jane")
+    ///         .build();
+    ///
+    /// assert_eq!(query, Err(QueryError::UnboundParameter(String::from("age"))));
+    /// ```
+    pub fn builder(query: &str) -> QueryBuilder {
+        QueryBuilder {
+            query: Query::new(query),
         }
     }
 
+    /// The names of all parameters used in the query, i.e. every `$name` outside of string
+    /// literals.
+    pub fn used_params(&self) -> Vec<String> {
+        let mut params: Vec<String> = Vec::new();
+        let mut chars = self.str.chars().peekable();
+        let mut quote = None;
+        while let Some(c) = chars.next() {
+            match (quote, c) {
+                (Some(q), '\\') if q != '`' => { chars.next(); }
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '\'') | (None, '"') | (None, '`') => quote = Some(c),
+                (None, '$') => {
+                    let mut name = String::new();
+                    if chars.peek() == Some(&'`') {
+                        chars.next();
+                        name.extend(chars.by_ref().take_while(|c| *c != '`'));
+                    } else {
+                        while let Some(c) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_') {
+                            name.push(*c);
+                            chars.next();
+                        }
+                    }
+
+                    if !name.is_empty() && !params.contains(&name) {
+                        params.push(name);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        params
+    }
+
     pub fn param<V: Into<Value<StdStruct>>>(&mut self, param: &str, value: V){
         self.parameters.add_property(param, value);
     }
+
+    /// Sets a parameter for every entry of a map.
+    pub fn param_map<M: Into<ParamMap>>(&mut self, params: M) {
+        for (param, value) in params.into().0 {
+            self.parameters.add_property(&param, value);
+        }
+    }
+
+    /// Sets a parameter to a list of maps, one per row, as used by `UNWIND`:
+    /// ```
+    /// # use raio::messaging::query::Query;
+    /// # use std::collections::HashMap;
+    /// let rows: Vec<HashMap<String, i64>> =
+    ///     (1..=3).map(|i| vec![(String::from("id"), i)].into_iter().collect()).collect();
+    ///
+    /// let mut query = Query::new("UNWIND $rows AS row CREATE (:Item {id: row.id})");
+    /// query.param_rows("rows", rows);
+    ///
+    /// assert_eq!(query.parameters().len(), 1);
+    /// ```
+    pub fn param_rows<I, M>(&mut self, param: &str, rows: I)
+        where I: IntoIterator<Item = M>, M: Into<ParamMap>
+    {
+        let rows: Vec<Value<StdStruct>> = rows.into_iter().map(|r| Value::from(r.into())).collect();
+        self.parameters.add_property(param, rows);
+    }
+
+    /// Sets a parameter to any serializable value, like a struct which becomes a dictionary or a
+    /// `Vec` of structs for an `UNWIND`.
+    #[cfg(feature = "serde")]
+    pub fn param_struct<T: serde::Serialize + ?Sized>(&mut self, param: &str, value: &T) -> Result<(), SerdeError> {
+        self.parameters.add_property(param, to_value(value)?);
+        Ok(())
+    }
+
+    /// Sets a parameter for every field of a serializable struct or map.
+    #[cfg(feature = "serde")]
+    pub fn params_from<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        for (param, value) in to_dictionary(value)? {
+            self.parameters.add_property(&param, value);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A map of parameter values, which can be built from the standard maps with `String` keys and
+/// used as a parameter value itself:
+/// ```
+/// # use raio::messaging::query::{ParamMap, Query};
+/// # use std::collections::BTreeMap;
+/// let mut address = BTreeMap::new();
+/// address.insert(String::from("city"), "Berlin");
+///
+/// let mut query = Query::new("CREATE (:Person {name: $name})-[:LIVES_AT]->(:Address $address)");
+/// query.param("name", "Jane");
+/// query.param("address", ParamMap::from(address));
+/// ```
+pub struct ParamMap(pub Dictionary<StdStruct>);
+
+impl Default for ParamMap {
+    fn default() -> Self {
+        ParamMap::new()
+    }
+}
+
+impl ParamMap {
+    pub fn new() -> Self {
+        ParamMap(Dictionary::new())
+    }
+
+    pub fn insert<V: Into<Value<StdStruct>>>(&mut self, key: &str, value: V) {
+        self.0.add_property(key, value);
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value<StdStruct>> {
+        self.0.get_property(key)
+    }
+
+    /// Converts the value of `key` into `T`, or returns `default` if there is none or it is
+    /// `null`:
+    /// ```
+    /// # use raio::messaging::query::ParamMap;
+    /// let mut params = ParamMap::new();
+    /// params.insert("limit", 10);
+    ///
+    /// assert_eq!(params.get_or("limit", 100u32), Ok(10));
+    /// assert_eq!(params.get_or("skip", 0u32), Ok(0));
+    /// assert!(params.get_or("limit", String::new()).is_err());
+    /// ```
+    pub fn get_or<T: FromValue>(&self, key: &str, default: T) -> Result<T, ValueError> {
+        match self.0.get_property(key) {
+            None | Some(Value::Null) => Ok(default),
+            Some(value) => T::from_value(value),
+        }
+    }
+
+    /// Removes the value of `key` and converts it into `T`; is `None` if there is none.
+    pub fn remove_value<T: FromValue>(&mut self, key: &str) -> Option<Result<T, ValueError>> {
+        self.0.extract_property(key).map(|value| T::from_value(&value))
+    }
+
+    /// Adds all entries of `other`, replacing entries with the same key.
+    pub fn merge(&mut self, other: ParamMap) {
+        for (key, value) in other.0 {
+            self.0.add_property(&key, value);
+        }
+    }
+
+    /// Converts every value into `T`, failing on the first which does not fit:
+    /// ```
+    /// # use raio::messaging::query::ParamMap;
+    /// let mut params = ParamMap::new();
+    /// params.insert("a", 1);
+    /// params.insert("b", 2);
+    ///
+    /// let map = params.clone().try_into_map::<u8>().unwrap();
+    /// assert_eq!(map["b"], 2);
+    ///
+    /// params.insert("c", "three");
+    /// assert!(params.try_into_map::<u8>().is_err());
+    /// ```
+    pub fn try_into_map<T: FromValue>(self) -> Result<HashMap<String, T>, ConversionError> {
+        let mut map = HashMap::with_capacity(self.0.len());
+        for (key, value) in self.0 {
+            match T::from_value(&value) {
+                Ok(v) => { map.insert(key, v); }
+                Err(error) => return Err(ConversionError::InvalidValue { field: key, error }),
+            }
+        }
+
+        Ok(map)
+    }
+}
+
+impl From<Dictionary<StdStruct>> for ParamMap {
+    fn from(dict: Dictionary<StdStruct>) -> Self {
+        ParamMap(dict)
+    }
+}
+
+impl<V: Into<Value<StdStruct>>> From<HashMap<String, V>> for ParamMap {
+    fn from(map: HashMap<String, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<V: Into<Value<StdStruct>>> From<BTreeMap<String, V>> for ParamMap {
+    fn from(map: BTreeMap<String, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<V: Into<Value<StdStruct>>> FromIterator<(String, V)> for ParamMap {
+    fn from_iter<I: IntoIterator<Item = (String, V)>>(iter: I) -> Self {
+        let mut params = ParamMap::new();
+        for (key, value) in iter {
+            params.insert(&key, value);
+        }
+        params
+    }
+}
+
+impl From<ParamMap> for Value<StdStruct> {
+    fn from(params: ParamMap) -> Self {
+        Value::Dictionary(params.0)
+    }
+}
+
+/// A fluent builder of a `Query`, see [`Query::builder`](crate::messaging::query::Query::builder).
+pub struct QueryBuilder {
+    query: Query,
+}
+
+impl QueryBuilder {
+    pub fn param<V: Into<Value<StdStruct>>>(mut self, param: &str, value: V) -> Self {
+        self.query.param(param, value);
+        self
+    }
+
+    /// Builds the query; fails if a parameter used in the query is not bound.
+    pub fn build(self) -> Result<Query, QueryError> {
+        for param in self.query.used_params() {
+            if !self.query.parameters.has_property(&param) {
+                return Err(QueryError::UnboundParameter(param));
+            }
+        }
+
+        Ok(self.query)
+    }
+}
+
+/// A cache of the most recently prepared queries, keyed by their statement. When full, the least
+/// recently used query is dropped:
+/// ```
+/// # use raio::messaging::query::StatementCache;
+/// let mut cache = StatementCache::new(2);
+/// cache.prepare("RETURN 1");
+/// cache.prepare("RETURN 2");
+/// cache.prepare("RETURN 1");
+/// cache.prepare("RETURN 3");
+///
+/// assert_eq!(cache.len(), 2);
+/// assert!(cache.contains("RETURN 1"));
+/// assert!(!cache.contains("RETURN 2"));
+/// assert!(cache.contains("RETURN 3"));
+/// ```
+#[derive(Debug)]
+pub struct StatementCache {
+    capacity: usize,
+    uses: u64,
+    // the encoded statements together with their last use:
+    queries: HashMap<String, (Arc<[u8]>, u64)>,
+    // the statements by their last use, least recent first:
+    recency: BTreeMap<u64, String>,
+}
+
+impl StatementCache {
+    pub fn new(capacity: usize) -> Self {
+        StatementCache {
+            capacity,
+            uses: 0,
+            queries: HashMap::with_capacity(capacity),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    /// A prepared query without parameters for `statement`, taken from the cache if possible.
+    pub fn prepare(&mut self, statement: &str) -> Query {
+        self.uses += 1;
+        if let Some((encoded, last_use)) = self.queries.get_mut(statement) {
+            self.recency.remove(last_use);
+            self.recency.insert(self.uses, String::from(statement));
+            *last_use = self.uses;
+            return Query {
+                str: String::from(statement),
+                parameters: Dictionary::new(),
+                encoded: Some(encoded.clone()),
+            };
+        }
+
+        let query = Query::prepare(statement);
+        if let (Some(encoded), true) = (&query.encoded, self.capacity > 0) {
+            if self.queries.len() >= self.capacity {
+                let least_recent = self.recency.keys().next().copied();
+                if let Some(statement) = least_recent.and_then(|last_use| self.recency.remove(&last_use)) {
+                    self.queries.remove(&statement);
+                }
+            }
+
+            self.queries.insert(String::from(statement), (encoded.clone(), self.uses));
+            self.recency.insert(self.uses, String::from(statement));
+        }
+        query
+    }
+
+    /// Checks if a prepared query for `statement` is cached.
+    pub fn contains(&self, statement: &str) -> bool {
+        self.queries.contains_key(statement)
+    }
+
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
 }
 
 pub(crate) fn query_pack_flat<T: Write>(query: &Query, writer: &mut T) -> Result<usize, EncodeError> {
-    Ok(query.str.encode(writer)? + query.parameters.encode(writer)?)
+    let statement = match &query.encoded {
+        Some(encoded) => {
+            writer.write_all(encoded)?;
+            encoded.len()
+        }
+        None => query.str.encode(writer)?,
+    };
+
+    Ok(statement + query.parameters.encode(writer)?)
 }
@@ -1,7 +1,9 @@
 use packs::std_structs::StdStruct;
 use packs::*;
-use crate::messaging::commit_prepare::CommitPrepare;
+use crate::messaging::commit_prepare::{CommitPrepare, CommitMode};
 use crate::messaging::query::{Query, query_pack_flat};
+use crate::messaging::bookmark::Bookmark;
+use crate::connectivity::version::Version;
 
 #[derive(Debug, Clone, PartialEq, Pack)]
 #[tag = 0x01]
@@ -22,8 +24,85 @@ impl Hello {
          extra,
       }
    }
+
+   /// Creates a `HELLO` without any authentication, which is needed since 5.1 where
+   /// authentication is done by a separate `LOGON`.
+   pub fn without_auth(agent_name: &str, version: &str) -> Self {
+      let mut extra = <Dictionary<StdStruct>>::with_capacity(1);
+      extra.add_property("user_agent", format!("{}/{}", agent_name, version));
+
+      Hello {
+         extra,
+      }
+   }
+
+   /// Adds the `bolt_agent` (since 5.3), which describes the driver, i.e. raio, in addition to the
+   /// `user_agent` of the application:
+   /// ```
+   /// # use raio::messaging::request::Hello;
+   /// # use packs::Dictionary;
+   /// # use packs::std_structs::StdStruct;
+   /// let mut hello = Hello::without_auth("my-app", "1.0");
+   /// hello.set_bolt_agent();
+   ///
+   /// let agent: &Dictionary<StdStruct> = hello.extra().get_property_typed("bolt_agent").unwrap();
+   /// assert!(agent.get_property_typed::<String>("product").unwrap().starts_with("raio/"));
+   /// ```
+   pub fn set_bolt_agent(&mut self) -> &mut Self {
+      let mut agent = <Dictionary<StdStruct>>::with_capacity(3);
+      agent.add_property("product", format!("raio/{}", env!("CARGO_PKG_VERSION")));
+      agent.add_property("platform", format!("{}; {}", std::env::consts::OS, std::env::consts::ARCH));
+      agent.add_property("language", "Rust");
+      self.extra.add_property("bolt_agent", agent);
+      self
+   }
+
+   /// Asks the server to apply the provided patches to the protocol (4.3 and 4.4 only), e.g.
+   /// `utc`, which encodes date times with zone in UTC.
+   pub fn set_patch_bolt(&mut self, patches: &[&str]) -> &mut Self {
+      let patches: Vec<Value<StdStruct>> = patches.iter().map(|p| Value::from(*p)).collect();
+      self.extra.add_property("patch_bolt", patches);
+      self
+   }
+
+   pub fn extra(&self) -> &Dictionary<StdStruct> {
+      &self.extra
+   }
+
+   /// Sets the routing context (since 4.1), which tells the server that the client routes, such
+   /// that a server behind a load balancer can route on its side.
+   pub fn set_routing(&mut self, routing: Dictionary<StdStruct>) -> &mut Self {
+      self.extra.add_property("routing", routing);
+      self
+   }
+}
+
+#[derive(Debug, Clone, PartialEq, Pack)]
+#[tag = 0x6A]
+/// The `LOGON` request which authenticates a connection since 5.1.
+pub struct Logon {
+   auth: Dictionary<StdStruct>,
+}
+
+impl Logon {
+   pub fn new(scheme: &str, principal: &str, credentials: &str) -> Self {
+      let mut auth = <Dictionary<StdStruct>>::with_capacity(3);
+      auth.add_property("scheme", scheme);
+      auth.add_property("principal", principal);
+      auth.add_property("credentials", credentials);
+
+      Logon {
+         auth,
+      }
+   }
 }
 
+#[derive(Debug, Clone, PartialEq, Pack)]
+#[tag = 0x6B]
+/// The `LOGOFF` request which de-authenticates a connection since 5.1, such that it can be
+/// authenticated again with a `LOGON`.
+pub struct Logoff {}
+
 #[derive(Debug, Clone, PartialEq, Pack)]
 #[tag = 0x02]
 pub struct GoodBye {}
@@ -54,6 +133,20 @@ impl<'a> Run<'a> {
    pub fn commit_prepare(&mut self) -> &mut CommitPrepare {
       &mut self.extra
    }
+
+   pub fn query(&self) -> &Query {
+      self.query
+   }
+
+   /// The commit mode set for this `RUN`, if any.
+   pub fn mode(&self) -> Option<CommitMode> {
+      self.extra.mode
+   }
+
+   /// The database set for this `RUN`, if any.
+   pub fn db(&self) -> Option<&str> {
+      self.extra.db.as_deref()
+   }
 }
 
 
@@ -161,4 +254,56 @@ pub struct Commit {}
 
 #[derive(Debug, Clone, PartialEq, Pack)]
 #[tag = 0x13]
-pub struct RollBack {}
\ No newline at end of file
+pub struct RollBack {}
+#[derive(Debug, Clone, PartialEq, Pack)]
+#[tag = 0x66]
+/// The `ROUTE` request which asks the server for a routing table of a cluster. The `routing`
+/// context is usually the one given by the address the client connected to, `db` selects the
+/// database to route for; `None` requests the default database.
+pub struct Route {
+   routing: Dictionary<StdStruct>,
+   bookmarks: Vec<String>,
+   db: Value<StdStruct>,
+}
+
+impl Route {
+   /// Creates a `ROUTE` request with the provided routing context and database name:
+   /// ```
+   /// # use raio::messaging::request::Route;
+   /// # use packs::Dictionary;
+   /// let mut context = Dictionary::new();
+   /// context.add_property("address", "localhost:7687");
+   ///
+   /// let route = Route::new(context, Some("neo4j"));
+   /// assert_eq!(route.routing().get_property_typed("address"), Some(&String::from("localhost:7687")));
+   /// ```
+   pub fn new(routing: Dictionary<StdStruct>, db: Option<&str>) -> Self {
+      Route {
+         routing,
+         bookmarks: Vec::new(),
+         db: db.map(String::from).into(),
+      }
+   }
+
+   pub fn routing(&self) -> &Dictionary<StdStruct> {
+      &self.routing
+   }
+
+   pub fn add_bookmark(&mut self, bookmark: Bookmark) -> &mut Self {
+      self.bookmarks.push(bookmark.into_inner());
+      self
+   }
+
+   /// Since 4.4 the database is sent within an extra dictionary instead of as a plain string.
+   pub fn for_version(mut self, version: Version) -> Self {
+      let converted = <Dictionary<StdStruct>>::extract_ref(&self.db).is_some();
+      if version >= Version::new(4, 4) && !converted {
+         let mut extra = <Dictionary<StdStruct>>::with_capacity(1);
+         if let Some(db) = String::extract_ref(&self.db) {
+            extra.add_property("db", db.as_str());
+         }
+         self.db = extra.into();
+      }
+      self
+   }
+}
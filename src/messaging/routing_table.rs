@@ -0,0 +1,49 @@
+use packs::{Dictionary, extract_list};
+use packs::std_structs::StdStructPrimitive;
+use crate::messaging::response::Success;
+
+#[derive(Debug, Clone, PartialEq)]
+/// The routing table of a cluster as it is returned in the `SUCCESS` of a `ROUTE` request. Each
+/// server is given by its address `host:port`, grouped by its role.
+pub struct RoutingTable {
+    pub ttl: i64,
+    pub db: Option<String>,
+    pub routers: Vec<String>,
+    pub readers: Vec<String>,
+    pub writers: Vec<String>,
+}
+
+impl RoutingTable {
+    /// Reads out the `rt` entry of a `SUCCESS`. Returns `None` if there is no routing table or
+    /// it is malformed.
+    pub fn from_success(mut s: Success) -> Option<Self> {
+        let mut rt: Dictionary<StdStructPrimitive> = s.metadata.extract_property_typed("rt")?;
+        let ttl = rt.extract_property_typed("ttl")?;
+        let db = rt.extract_property_typed("db");
+        let servers: Vec<Dictionary<StdStructPrimitive>> =
+            rt.extract_property("servers").and_then(extract_list)?;
+
+        let mut table = RoutingTable {
+            ttl,
+            db,
+            routers: Vec::new(),
+            readers: Vec::new(),
+            writers: Vec::new(),
+        };
+
+        for mut server in servers.into_iter() {
+            let role: String = server.extract_property_typed("role")?;
+            let addresses: Vec<String> =
+                server.extract_property("addresses").and_then(extract_list)?;
+
+            match role.as_str() {
+                "ROUTE" => table.routers.extend(addresses),
+                "READ" => table.readers.extend(addresses),
+                "WRITE" => table.writers.extend(addresses),
+                _ => {}
+            }
+        }
+
+        Some(table)
+    }
+}
@@ -0,0 +1,566 @@
+use std::fmt;
+
+use packs::{Dictionary, Value};
+use packs::std_structs::{StdStruct, Node, Relationship, UnboundRelationship};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::ser::{
+    self, Serialize, Serializer, SerializeMap, SerializeSeq, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+};
+use serde::forward_to_deserialize_any;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{0}")]
+/// An error while converting between bolt values and serde types.
+pub struct SerdeError(pub String);
+
+impl de::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerdeError(msg.to_string())
+    }
+}
+
+impl ser::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerdeError(msg.to_string())
+    }
+}
+
+/// Serializes a bolt value. Nodes and relationships are serialized as maps of their id, labels or
+/// type and properties, paths as a map of their nodes and relationships:
+/// ```
+/// # use raio::messaging::serialization::SerializeValue;
+/// # use packs::Value;
+/// # use packs::std_structs::StdStruct;
+/// let value = <Value<StdStruct>>::from(vec![Value::from(1), Value::from("two"), Value::Null]);
+///
+/// let json = serde_json::to_string(&SerializeValue(&value)).unwrap();
+/// assert_eq!(json, r#"[1,"two",null]"#);
+/// ```
+pub struct SerializeValue<'a>(pub &'a Value<StdStruct>);
+
+impl<'a> Serialize for SerializeValue<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Value::Null => serializer.serialize_none(),
+            Value::Boolean(b) => serializer.serialize_bool(*b),
+            Value::Integer(i) => serializer.serialize_i64(*i),
+            Value::Float(f) => serializer.serialize_f64(*f),
+            Value::Bytes(b) => serializer.serialize_bytes(b),
+            Value::String(s) => serializer.serialize_str(s),
+            Value::List(l) => {
+                let mut seq = serializer.serialize_seq(Some(l.len()))?;
+                for v in l.iter() {
+                    seq.serialize_element(&SerializeValue(v))?;
+                }
+                seq.end()
+            }
+            Value::Dictionary(d) => SerializeDictionary(d).serialize(serializer),
+            Value::Structure(s) => SerializeStruct(s).serialize(serializer),
+        }
+    }
+}
+
+/// Serializes a dictionary of bolt values as a map.
+pub struct SerializeDictionary<'a>(pub &'a Dictionary<StdStruct>);
+
+impl<'a> Serialize for SerializeDictionary<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (k, v) in self.0.iter() {
+            map.serialize_entry(k, &SerializeValue(v))?;
+        }
+        map.end()
+    }
+}
+
+struct SerializeStruct<'a>(&'a StdStruct);
+
+impl<'a> Serialize for SerializeStruct<'a> {
+    #[allow(unreachable_patterns)]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            StdStruct::Node(n) => {
+                let mut map = serializer.serialize_map(Some(3))?;
+                map.serialize_entry("id", &n.id)?;
+                map.serialize_entry("labels", &n.labels)?;
+                map.serialize_entry("properties", &SerializeDictionary(&n.properties))?;
+                map.end()
+            }
+            StdStruct::Relationship(r) => {
+                let mut map = serializer.serialize_map(Some(5))?;
+                map.serialize_entry("id", &r.id)?;
+                map.serialize_entry("start", &r.start_node_id)?;
+                map.serialize_entry("end", &r.end_node_id)?;
+                map.serialize_entry("type", &r._type)?;
+                map.serialize_entry("properties", &SerializeDictionary(&r.properties))?;
+                map.end()
+            }
+            StdStruct::UnboundRelationship(r) => {
+                let mut map = serializer.serialize_map(Some(3))?;
+                map.serialize_entry("id", &r.id)?;
+                map.serialize_entry("type", &r._type)?;
+                map.serialize_entry("properties", &SerializeDictionary(&r.properties))?;
+                map.end()
+            }
+            StdStruct::Path(p) => {
+                let nodes: Vec<Value<StdStruct>> = p.nodes.iter().cloned().map(Value::from).collect();
+                let rels: Vec<Value<StdStruct>> = p.rels.iter().cloned().map(Value::from).collect();
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("nodes", &SerializeValue(&Value::List(nodes)))?;
+                map.serialize_entry("relationships", &SerializeValue(&Value::List(rels)))?;
+                map.end()
+            }
+            _ => Err(ser::Error::custom("structure cannot be serialized")),
+        }
+    }
+}
+
+/// Deserializes any serde type out of a bolt value. Nodes and relationships deserialize from
+/// their properties, such that a node can be turned into a struct of its properties.
+pub struct ValueDeserializer(pub Value<StdStruct>);
+
+/// Deserializes `T` out of the provided bolt value:
+/// ```
+/// # use raio::messaging::serialization::from_value;
+/// # use packs::{Value, Dictionary};
+/// # use packs::std_structs::StdStruct;
+/// #[derive(serde::Deserialize)]
+/// struct Person {
+///     name: String,
+///     age: i64,
+///     email: Option<String>,
+/// }
+///
+/// let mut dict = <Dictionary<StdStruct>>::new();
+/// dict.add_property("name", "Jane");
+/// dict.add_property("age", 42);
+///
+/// let person: Person = from_value(Value::Dictionary(dict)).unwrap();
+/// assert_eq!(person.name, "Jane");
+/// assert_eq!(person.age, 42);
+/// assert_eq!(person.email, None);
+/// ```
+pub fn from_value<T: DeserializeOwned>(value: Value<StdStruct>) -> Result<T, SerdeError> {
+    T::deserialize(ValueDeserializer(value))
+}
+
+/// Deserializes `T` out of a dictionary of bolt values, see
+/// [`from_value`](crate::messaging::serialization::from_value).
+pub fn from_dictionary<T: DeserializeOwned>(dict: Dictionary<StdStruct>) -> Result<T, SerdeError> {
+    from_value(Value::Dictionary(dict))
+}
+
+/// Deserializes the properties of a node or relationship into `T`, using the property names as
+/// keys:
+/// ```
+/// # use raio::messaging::serialization::DeserializeProperties;
+/// # use packs::std_structs::Node;
+/// #[derive(serde::Deserialize)]
+/// struct Person {
+///     name: String,
+///     email: Option<String>,
+/// }
+///
+/// let mut node = Node::new(1);
+/// node.properties.add_property("name", "Jane");
+///
+/// let person: Person = node.to_properties().unwrap();
+/// assert_eq!(person.name, "Jane");
+/// assert_eq!(person.email, None);
+/// ```
+pub trait DeserializeProperties {
+    fn property_dictionary(&self) -> &Dictionary<StdStruct>;
+
+    fn to_properties<T: DeserializeOwned>(&self) -> Result<T, SerdeError> {
+        from_dictionary(self.property_dictionary().clone())
+    }
+}
+
+impl DeserializeProperties for Node {
+    fn property_dictionary(&self) -> &Dictionary<StdStruct> {
+        &self.properties
+    }
+}
+
+impl DeserializeProperties for Relationship {
+    fn property_dictionary(&self) -> &Dictionary<StdStruct> {
+        &self.properties
+    }
+}
+
+impl DeserializeProperties for UnboundRelationship {
+    fn property_dictionary(&self) -> &Dictionary<StdStruct> {
+        &self.properties
+    }
+}
+
+impl<'de> IntoDeserializer<'de, SerdeError> for ValueDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+fn visit_dictionary<'de, V: Visitor<'de>>(dict: Dictionary<StdStruct>, visitor: V) -> Result<V::Value, SerdeError> {
+    let entries = dict.into_iter().map(|(k, v)| (k, ValueDeserializer(v)));
+    let mut map = MapDeserializer::new(entries);
+    let value = visitor.visit_map(&mut map)?;
+    map.end()?;
+    Ok(value)
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer {
+    type Error = SerdeError;
+
+    #[allow(unreachable_patterns)]
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Null => visitor.visit_unit(),
+            Value::Boolean(b) => visitor.visit_bool(b),
+            Value::Integer(i) => visitor.visit_i64(i),
+            Value::Float(f) => visitor.visit_f64(f),
+            Value::Bytes(b) => visitor.visit_byte_buf(b),
+            Value::String(s) => visitor.visit_string(s),
+            Value::List(l) => {
+                let mut seq = SeqDeserializer::new(l.into_iter().map(ValueDeserializer));
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Dictionary(d) => visit_dictionary(d, visitor),
+            Value::Structure(StdStruct::Node(n)) => visit_dictionary(n.properties, visitor),
+            Value::Structure(StdStruct::Relationship(r)) => visit_dictionary(r.properties, visitor),
+            Value::Structure(StdStruct::UnboundRelationship(r)) => visit_dictionary(r.properties, visitor),
+            Value::Structure(_) => Err(de::Error::custom("structure cannot be deserialized")),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// Serializes any serde type into a bolt value, e.g. to use it as a query parameter. Structs and
+/// maps become dictionaries, sequences and tuples become lists:
+/// ```
+/// # use raio::messaging::serialization::to_value;
+/// # use packs::{Value, Dictionary};
+/// # use packs::std_structs::StdStruct;
+/// #[derive(serde::Serialize)]
+/// struct Person {
+///     name: String,
+///     tags: Vec<String>,
+/// }
+///
+/// let person = Person { name: String::from("Jane"), tags: vec![String::from("admin")] };
+/// let value = to_value(&person).unwrap();
+///
+/// let mut dict = <Dictionary<StdStruct>>::new();
+/// dict.add_property("name", "Jane");
+/// dict.add_property("tags", vec![Value::from("admin")]);
+/// assert_eq!(value, Value::Dictionary(dict));
+/// ```
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value<StdStruct>, SerdeError> {
+    value.serialize(ValueSerializer)
+}
+
+/// Serializes any serde type into a dictionary of bolt values; fails if `value` does not
+/// serialize as a map or struct.
+pub fn to_dictionary<T: Serialize + ?Sized>(value: &T) -> Result<Dictionary<StdStruct>, SerdeError> {
+    match to_value(value)? {
+        Value::Dictionary(dict) => Ok(dict),
+        _ => Err(SerdeError(String::from("expected a map or struct"))),
+    }
+}
+
+/// A serde `Serializer` which produces bolt values.
+pub struct ValueSerializer;
+
+/// Collects the elements of a sequence, tuple or tuple variant.
+pub struct ListSerializer {
+    variant: Option<&'static str>,
+    list: Vec<Value<StdStruct>>,
+}
+
+/// Collects the entries of a map, struct or struct variant.
+pub struct DictionarySerializer {
+    variant: Option<&'static str>,
+    dict: Dictionary<StdStruct>,
+    key: Option<String>,
+}
+
+/// Wraps `value` as `{variant: value}`, the bolt representation of enum variants with data.
+fn variant_value(variant: Option<&'static str>, value: Value<StdStruct>) -> Value<StdStruct> {
+    match variant {
+        Some(variant) => {
+            let mut dict = Dictionary::with_capacity(1);
+            dict.add_property(variant, value);
+            Value::Dictionary(dict)
+        }
+        None => value,
+    }
+}
+
+impl Serializer for ValueSerializer {
+    type Ok = Value<StdStruct>;
+    type Error = SerdeError;
+    type SerializeSeq = ListSerializer;
+    type SerializeTuple = ListSerializer;
+    type SerializeTupleStruct = ListSerializer;
+    type SerializeTupleVariant = ListSerializer;
+    type SerializeMap = DictionarySerializer;
+    type SerializeStruct = DictionarySerializer;
+    type SerializeStructVariant = DictionarySerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Boolean(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(i64::from(v)))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(i64::from(v)))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(i64::from(v)))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(i64::from(v)))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(i64::from(v)))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Integer(i64::from(v)))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        if v > i64::MAX as u64 {
+            return Err(SerdeError(format!("{} does not fit into a bolt integer", v)));
+        }
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Float(f64::from(v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::String(String::from(v)))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Bytes(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::String(String::from(variant)))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, variant: &'static str, value: &T) -> Result<Self::Ok, Self::Error> {
+        Ok(variant_value(Some(variant), value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(ListSerializer {
+            variant: None,
+            list: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _index: u32, variant: &'static str, len: usize) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Ok(ListSerializer {
+            variant: Some(variant),
+            list: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(DictionarySerializer {
+            variant: None,
+            dict: Dictionary::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct, Self::Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _index: u32, variant: &'static str, len: usize) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(DictionarySerializer {
+            variant: Some(variant),
+            dict: Dictionary::with_capacity(len),
+            key: None,
+        })
+    }
+}
+
+impl SerializeSeq for ListSerializer {
+    type Ok = Value<StdStruct>;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.list.push(to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(variant_value(self.variant, Value::List(self.list)))
+    }
+}
+
+impl SerializeTuple for ListSerializer {
+    type Ok = Value<StdStruct>;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeTupleStruct for ListSerializer {
+    type Ok = Value<StdStruct>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeTupleVariant for ListSerializer {
+    type Ok = Value<StdStruct>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeMap for DictionarySerializer {
+    type Ok = Value<StdStruct>;
+    type Error = SerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        match to_value(key)? {
+            Value::String(key) => {
+                self.key = Some(key);
+                Ok(())
+            }
+            _ => Err(SerdeError(String::from("map keys must be strings"))),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let key = self.key.take().ok_or_else(|| SerdeError(String::from("map value without key")))?;
+        self.dict.add_property(&key, to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(variant_value(self.variant, Value::Dictionary(self.dict)))
+    }
+}
+
+impl ser::SerializeStruct for DictionarySerializer {
+    type Ok = Value<StdStruct>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+        self.dict.add_property(key, to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        SerializeMap::end(self)
+    }
+}
+
+impl SerializeStructVariant for DictionarySerializer {
+    type Ok = Value<StdStruct>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+        self.dict.add_property(key, to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        SerializeMap::end(self)
+    }
+}
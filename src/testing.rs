@@ -0,0 +1,298 @@
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use async_std::io::{BufReader, BufWriter};
+use async_std::net::{TcpListener, TcpStream};
+use async_std::prelude::*;
+use async_std::task;
+use packs::{Dictionary, Marker, Pack, Unpack, Value};
+use packs::std_structs::StdStruct;
+use std::io::{Read, Write};
+use thiserror::Error;
+
+use crate::connectivity::version::Version;
+use crate::messaging::message::Message;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// The kind of a request, as given by its structure tag.
+pub enum RequestKind {
+    Hello,
+    Logon,
+    Logoff,
+    GoodBye,
+    Reset,
+    Run,
+    Discard,
+    Pull,
+    Begin,
+    Commit,
+    RollBack,
+    Route,
+    Unknown(u8),
+}
+
+impl RequestKind {
+    fn from_tag(tag: u8) -> Self {
+        match tag {
+            0x01 => RequestKind::Hello,
+            0x6A => RequestKind::Logon,
+            0x6B => RequestKind::Logoff,
+            0x02 => RequestKind::GoodBye,
+            0x0F => RequestKind::Reset,
+            0x10 => RequestKind::Run,
+            0x2F => RequestKind::Discard,
+            0x3F => RequestKind::Pull,
+            0x11 => RequestKind::Begin,
+            0x12 => RequestKind::Commit,
+            0x13 => RequestKind::RollBack,
+            0x66 => RequestKind::Route,
+            t => RequestKind::Unknown(t),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A request as received by the [`MockServer`](crate::testing::MockServer).
+pub struct ReceivedRequest {
+    pub kind: RequestKind,
+    pub fields: Vec<Value<StdStruct>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A response the [`MockServer`](crate::testing::MockServer) sends.
+pub enum Reply {
+    Success(Dictionary<StdStruct>),
+    Record(Vec<Value<StdStruct>>),
+    Failure { code: String, message: String },
+    Ignored,
+}
+
+impl Reply {
+    /// A `SUCCESS` without metadata.
+    pub fn success() -> Self {
+        Reply::Success(Dictionary::new())
+    }
+
+    /// A `SUCCESS` as it answers a `RUN`, announcing the provided fields.
+    pub fn fields(fields: &[&str]) -> Self {
+        let mut metadata = Dictionary::new();
+        let fields: Vec<Value<StdStruct>> = fields.iter().map(|f| Value::from(*f)).collect();
+        metadata.add_property("fields", Value::from(fields));
+        Reply::Success(metadata)
+    }
+
+    pub fn record(data: Vec<Value<StdStruct>>) -> Self {
+        Reply::Record(data)
+    }
+
+    pub fn failure(code: &str, message: &str) -> Self {
+        Reply::Failure {
+            code: String::from(code),
+            message: String::from(message),
+        }
+    }
+
+    fn encode(&self, message: &mut Message) -> std::io::Result<()> {
+        let encoded = match self {
+            Reply::Success(metadata) =>
+                encode_struct(message, 0x70, &[metadata]),
+            Reply::Record(data) =>
+                encode_struct(message, 0x71, &[data]),
+            Reply::Failure { code, message: text } => {
+                let mut metadata: Dictionary<StdStruct> = Dictionary::with_capacity(2);
+                metadata.add_property("code", code.as_str());
+                metadata.add_property("message", text.as_str());
+                encode_struct(message, 0x7F, &[&metadata])
+            }
+            Reply::Ignored =>
+                encode_struct(message, 0x7E, &[]),
+        };
+
+        encoded.map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{:?}", e)))
+    }
+}
+
+fn encode_struct(message: &mut Message, tag: u8, fields: &[&dyn EncodeField]) -> Result<(), packs::EncodeError> {
+    Marker::TinyStruct(fields.len() as u8).encode(message)?;
+    Write::write_all(message, &[tag])?;
+    for field in fields {
+        field.encode_field(message)?;
+    }
+
+    Ok(())
+}
+
+trait EncodeField {
+    fn encode_field(&self, message: &mut Message) -> Result<usize, packs::EncodeError>;
+}
+
+impl<P: Pack> EncodeField for P {
+    fn encode_field(&self, message: &mut Message) -> Result<usize, packs::EncodeError> {
+        self.encode(message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum MockError {
+    #[error("Expected {expected:?} but received {received:?}")]
+    UnexpectedRequest { expected: Option<RequestKind>, received: RequestKind },
+    #[error("{0} expected requests were never received")]
+    Unfulfilled(usize),
+}
+
+#[derive(Default)]
+struct Script {
+    expected: VecDeque<(RequestKind, Vec<Reply>)>,
+    received: Vec<ReceivedRequest>,
+    errors: Vec<MockError>,
+}
+
+/// An in-process bolt server which answers requests with scripted responses, for testing code
+/// which uses a client without a running Neo4j:
+/// ```no_run
+/// # use packs::Value;
+/// # use raio::client::{Client, ClientConfig};
+/// # use raio::client::auth::Basic;
+/// # use raio::connectivity::version::Version;
+/// # use raio::messaging::query::Query;
+/// # use raio::testing::{MockServer, Reply, RequestKind};
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let server = MockServer::start(Version::new(4, 1)).await?;
+/// server.expect(RequestKind::Hello, vec![Reply::success()]);
+/// server.expect(RequestKind::Run, vec![Reply::fields(&["x"])]);
+/// server.expect(RequestKind::Pull, vec![Reply::record(vec![Value::from(1)]), Reply::success()]);
+///
+/// let client = Client::create(&server.endpoint(), Basic::new("neo4j", "test"), ClientConfig::default("test", "0.1"));
+/// let result = client.query(&Query::new("RETURN 1 as x")).await?;
+///
+/// assert_eq!(result.records().len(), 1);
+/// server.verify()?;
+/// # Ok(())
+/// # }
+/// ```
+/// Requests are expected in the scripted order, over all connections. `RESET` and `GOODBYE`
+/// are answered with a `SUCCESS` resp. by closing the connection unless they are scripted; any
+/// other unexpected request is answered with a `FAILURE` and reported by `verify`.
+pub struct MockServer {
+    address: SocketAddr,
+    script: Arc<Mutex<Script>>,
+}
+
+impl MockServer {
+    /// Starts a server on a free local port, which agrees on `version` in every handshake.
+    pub async fn start(version: Version) -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let script = Arc::new(Mutex::new(Script::default()));
+
+        let accepting = script.clone();
+        task::spawn(async move {
+            let mut incoming = listener.incoming();
+            while let Some(Ok(stream)) = incoming.next().await {
+                let script = accepting.clone();
+                task::spawn(async move {
+                    let _ = serve(stream, version, script).await;
+                });
+            }
+        });
+
+        Ok(MockServer { address, script })
+    }
+
+    /// The endpoint to connect to, like `127.0.0.1:37411`.
+    pub fn endpoint(&self) -> String {
+        self.address.to_string()
+    }
+
+    /// Expects a request of `kind` next and answers it with `replies`.
+    pub fn expect(&self, kind: RequestKind, replies: Vec<Reply>) -> &Self {
+        self.script.lock().unwrap().expected.push_back((kind, replies));
+        self
+    }
+
+    /// All requests received so far, in order.
+    pub fn received(&self) -> Vec<ReceivedRequest> {
+        self.script.lock().unwrap().received.clone()
+    }
+
+    /// Checks that every expected request was received and no unexpected one.
+    pub fn verify(&self) -> Result<(), MockError> {
+        let script = self.script.lock().unwrap();
+        if let Some(error) = script.errors.first() {
+            return Err(error.clone());
+        }
+
+        match script.expected.len() {
+            0 => Ok(()),
+            n => Err(MockError::Unfulfilled(n)),
+        }
+    }
+}
+
+async fn serve(stream: TcpStream, version: Version, script: Arc<Mutex<Script>>) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.clone());
+    let mut writer = BufWriter::new(stream);
+
+    // handshake: magic preamble and four proposed version ranges:
+    let mut proposal = [0u8; 20];
+    reader.read_exact(&mut proposal).await?;
+    if proposal[0..4] != [0x60, 0x60, 0xB0, 0x17] {
+        return Ok(());
+    }
+
+    writer.write_all(&version.encode()).await?;
+    writer.flush().await?;
+
+    loop {
+        let request = read_request(&mut reader).await?;
+        let replies = {
+            let mut script = script.lock().unwrap();
+            script.received.push(request.clone());
+            match script.expected.front() {
+                Some((kind, _)) if *kind == request.kind =>
+                    script.expected.pop_front().map(|(_, replies)| replies),
+                _ if request.kind == RequestKind::Reset =>
+                    Some(vec![Reply::success()]),
+                _ if request.kind == RequestKind::GoodBye =>
+                    None,
+                expected => {
+                    let expected = expected.map(|(kind, _)| *kind);
+                    script.errors.push(MockError::UnexpectedRequest { expected, received: request.kind });
+                    Some(vec![Reply::failure("Mock.ClientError.Request.Unexpected", "Unexpected request")])
+                }
+            }
+        };
+
+        let replies = match replies {
+            Some(replies) => replies,
+            None => return Ok(()),
+        };
+
+        for reply in replies {
+            let mut message = Message::new_alloc(1, 1400);
+            reply.encode(&mut message)?;
+            message.pack(&mut writer).await?;
+        }
+    }
+}
+
+async fn read_request<R: async_std::io::Read + Unpin>(reader: &mut R) -> std::io::Result<ReceivedRequest> {
+    let mut message = Message::unpack(reader).await?;
+    let invalid = |e| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{:?}", e));
+
+    let mut header = [0u8; 2];
+    Read::read_exact(&mut message, &mut header)?;
+    let field_count = header[0] & 0x0F;
+
+    let mut fields = Vec::with_capacity(field_count as usize);
+    for _ in 0..field_count {
+        fields.push(Value::decode(&mut message).map_err(invalid)?);
+    }
+
+    Ok(ReceivedRequest {
+        kind: RequestKind::from_tag(header[1]),
+        fields,
+    })
+}
@@ -0,0 +1,99 @@
+use packs::std_structs::{Node, Relationship};
+
+use crate::client::graph::Graph;
+use crate::client::record_result::RecordResult;
+
+#[derive(Debug, Clone, PartialEq)]
+/// An element with the same id in both graphs, whose labels resp. type, end nodes or properties
+/// differ.
+pub struct Change<T> {
+    pub before: T,
+    pub after: T,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+/// The differences between two graphs, by id; every list is ordered by id.
+pub struct GraphDiff {
+    pub added_nodes: Vec<Node>,
+    pub removed_nodes: Vec<Node>,
+    pub changed_nodes: Vec<Change<Node>>,
+    pub added_relationships: Vec<Relationship>,
+    pub removed_relationships: Vec<Relationship>,
+    pub changed_relationships: Vec<Change<Relationship>>,
+}
+
+impl GraphDiff {
+    /// Checks if both graphs are the same.
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.changed_nodes.is_empty()
+            && self.added_relationships.is_empty()
+            && self.removed_relationships.is_empty()
+            && self.changed_relationships.is_empty()
+    }
+}
+
+/// Compares the graphs `before` and `after`, e.g. the results of the same query before and after
+/// a data migration:
+/// ```
+/// # use raio::client::graph::Graph;
+/// # use raio::util::diff::diff;
+/// # use packs::std_structs::Node;
+/// let mut jane = Node::new(1);
+/// jane.properties.add_property("name", "Jane");
+///
+/// let mut before = Graph::new();
+/// before.add_node(&jane);
+/// before.add_node(&Node::new(2));
+///
+/// let mut renamed = jane.clone();
+/// renamed.properties.add_property("name", "Jane Doe");
+///
+/// let mut after = Graph::new();
+/// after.add_node(&renamed);
+/// after.add_node(&Node::new(3));
+///
+/// let diff = diff(&before, &after);
+/// assert_eq!(diff.added_nodes, vec![Node::new(3)]);
+/// assert_eq!(diff.removed_nodes, vec![Node::new(2)]);
+/// assert_eq!(diff.changed_nodes[0].after, renamed);
+/// assert!(diff.added_relationships.is_empty());
+/// ```
+pub fn diff(before: &Graph, after: &Graph) -> GraphDiff {
+    let mut diff = GraphDiff::default();
+
+    for node in after.nodes() {
+        match before.node(node.id) {
+            None => diff.added_nodes.push(node.clone()),
+            Some(old) if old != node =>
+                diff.changed_nodes.push(Change { before: old.clone(), after: node.clone() }),
+            Some(_) => {}
+        }
+    }
+    diff.removed_nodes = before.nodes().filter(|n| after.node(n.id).is_none()).cloned().collect();
+
+    for rel in after.relationships() {
+        match before.relationship(rel.id) {
+            None => diff.added_relationships.push(rel.clone()),
+            Some(old) if old != rel =>
+                diff.changed_relationships.push(Change { before: old.clone(), after: rel.clone() }),
+            Some(_) => {}
+        }
+    }
+    diff.removed_relationships =
+        before.relationships().filter(|r| after.relationship(r.id).is_none()).cloned().collect();
+
+    diff.added_nodes.sort_by_key(|n| n.id);
+    diff.removed_nodes.sort_by_key(|n| n.id);
+    diff.changed_nodes.sort_by_key(|c| c.after.id);
+    diff.added_relationships.sort_by_key(|r| r.id);
+    diff.removed_relationships.sort_by_key(|r| r.id);
+    diff.changed_relationships.sort_by_key(|c| c.after.id);
+    diff
+}
+
+/// Compares the graphs of two record sets, see [`Graph::from_records`].
+pub fn diff_records(before: &[RecordResult], after: &[RecordResult]) -> GraphDiff {
+    diff(&Graph::from_records(before), &Graph::from_records(after))
+}
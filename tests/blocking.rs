@@ -0,0 +1,27 @@
+#![cfg(feature = "blocking")]
+
+use raio::blocking::Client;
+use raio::client::ClientConfig;
+use raio::client::auth::Basic;
+use raio::client::error::ClientError;
+use raio::messaging::commit_prepare::CommitPrepare;
+use raio::messaging::query::Query;
+
+#[test]
+pub fn blocking_client() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let result = client.query(&Query::new("RETURN 1 as x"))?;
+    assert_eq!(result.records()[0].get_field_typed("x"), Some(&1));
+
+    let mut transaction = client.begin(CommitPrepare::new())?;
+    let result = transaction.run(&Query::new("RETURN 2 as x"))?;
+    assert_eq!(result.records()[0].get_field_typed("x"), Some(&2));
+    transaction.commit()?;
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use raio::client::{Client, ClientConfig};
 use raio::client::auth::Basic;
 use raio::client::error::ClientError;
+use raio::connectivity::connection::ConnectionError;
 use raio::messaging::query::Query;
 
 #[async_std::test]
@@ -34,4 +35,224 @@ pub async fn auto_commit_simple() -> Result<(), ClientError> {
     assert_eq!(first.get_field_typed("b"), Some(&true));
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[async_std::test]
+pub async fn close_client() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    client.query(&Query::new("RETURN 1 as x")).await?;
+    client.close(std::time::Duration::from_secs(5)).await?;
+
+    assert!(client.is_closed());
+    assert!(matches!(client.query(&Query::new("RETURN 1 as x")).await, Err(ClientError::Closed)));
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn pool_status() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    client.query(&Query::new("RETURN 1 as x")).await?;
+    client.query(&Query::new("RETURN 1 as x")).await?;
+
+    let status = client.pool_status();
+    assert_eq!(status.created, 1);
+    assert_eq!(status.recycled, 1);
+    assert_eq!(status.acquisitions, 2);
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+    assert_eq!(status.in_use, 0);
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn warm_up() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0").min_idle(3));
+
+    client.warm_up().await?;
+
+    let status = client.pool_status();
+    assert_eq!(status.size, 3);
+    assert_eq!(status.available, 3);
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn server_info() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let info = client.server_info().await?;
+    assert!(info.agent.unwrap().starts_with("Neo4j/"));
+    assert!(info.connection_id.is_some());
+    assert!(info.protocol_version.is_some());
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn verify_connectivity() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let connectivity = client.verify_connectivity().await?;
+    assert!(connectivity.server.agent.unwrap().starts_with("Neo4j/"));
+    assert!(connectivity.server.protocol_version.is_some());
+
+    let wrong =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "wrong"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+    assert!(wrong.verify_connectivity().await.is_err());
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn feature_detection() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let version = client.protocol_version().await?;
+    assert_eq!(client.supports_multi_db().await?, version.supports_multi_db());
+    assert_eq!(client.supports_route().await?, version.supports_route());
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn into_columns() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let result = client.query(&Query::new("UNWIND range(1, 5) AS x RETURN x, x * 2 AS y")).await?;
+    let columns = result.into_columns();
+    assert_eq!(columns.fields(), &[String::from("x"), String::from("y")]);
+    assert_eq!(columns.typed::<i64>("y")?, vec![2, 4, 6, 8, 10]);
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn path_segments() -> Result<(), ClientError> {
+    use packs::std_structs::Path;
+    use raio::client::path::{Direction, PathExt};
+    use raio::client::relationship::{RelationshipExt, UnboundRelationshipExt};
+
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let result =
+        client
+            .query(&Query::new(
+                "CREATE p = (a:RaioPathTest {n: 1})-[:NEXT]->(b:RaioPathTest {n: 2})<-[:NEXT]-(c:RaioPathTest {n: 3}) \
+                 RETURN p, id(a) AS a, id(b) AS b, id(c) AS c"))
+            .await?;
+    client.query(&Query::new("MATCH (n:RaioPathTest) DETACH DELETE n")).await?;
+
+    let record = result.records().first().expect("Expected a path.");
+    let path: &Path = record.get_field_typed("p").expect("Expected a path.");
+    let (a, b, c): (i64, i64, i64) =
+        (*record.get_field_typed("a").unwrap(), *record.get_field_typed("b").unwrap(), *record.get_field_typed("c").unwrap());
+
+    assert_eq!(path.len(), 2);
+    assert!(path.contains_node(c));
+    assert_eq!(path.start_node().map(|n| n.id), Some(a));
+    assert_eq!(path.end_node().map(|n| n.id), Some(c));
+
+    let walk: Vec<(i64, i64, Direction)> =
+        path.iter_segments().map(|s| (s.start.id, s.end.id, s.direction)).collect();
+    assert_eq!(walk, vec![(a, b, Direction::Forward), (b, c, Direction::Backward)]);
+
+    let second = path.iter_segments().nth(1).unwrap().bound_relationship();
+    assert_eq!((second.start_node_id, second.end_node_id), (c, b));
+    assert_eq!(path.rels[1].bind_in(path), Some(second.clone()));
+    assert_eq!(second.other_node(b), Some(c));
+    assert!(second.is_outgoing_from(c));
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn connect_backoff() -> Result<(), ClientError> {
+    use raio::connectivity::backoff::BackoffConfig;
+
+    // nothing listens on port 1:
+    let client =
+        Client::create(
+            "127.0.0.1:1",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0")
+                .connect_backoff(
+                    BackoffConfig::default()
+                        .initial_delay(std::time::Duration::from_secs(60))
+                        .max_failures(1)));
+
+    let first = client.query(&Query::new("RETURN 1 as x")).await.err();
+    assert!(matches!(first, Some(ClientError::ConnectionError(_))));
+
+    // the circuit is open now, the server is not asked again:
+    let second = client.query(&Query::new("RETURN 1 as x")).await.err();
+    assert!(matches!(second, Some(ClientError::ServerUnavailable)));
+    assert!(second.unwrap().is_retryable());
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn connect_backoff_create_timeout() -> Result<(), ClientError> {
+    use raio::connectivity::backoff::BackoffConfig;
+
+    // accepts connections, but never answers the handshake:
+    let listener = async_std::net::TcpListener::bind("127.0.0.1:0").await?;
+    let client =
+        Client::create(
+            &listener.local_addr()?.to_string(),
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0")
+                .create_timeout(Some(std::time::Duration::from_millis(200)))
+                .connect_backoff(
+                    BackoffConfig::default()
+                        .initial_delay(std::time::Duration::from_secs(60))
+                        .max_failures(1)));
+
+    let first = client.query(&Query::new("RETURN 1 as x")).await.err();
+    assert!(matches!(first, Some(ClientError::ConnectionError(ConnectionError::Timeout))));
+
+    let second = client.query(&Query::new("RETURN 1 as x")).await.err();
+    assert!(matches!(second, Some(ClientError::ServerUnavailable)));
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use raio::client::{Client, ClientConfig};
+use raio::client::auth::Basic;
+use raio::client::error::ClientError;
+use raio::client::record_result::ConversionError;
+use raio::messaging::query::Query;
+use raio_derive::FromRecord;
+
+#[derive(Debug, PartialEq, FromRecord)]
+struct Person {
+    name: String,
+    age: i64,
+    email: Option<String>,
+}
+
+#[async_std::test]
+pub async fn query_into_typed() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let query = Query::new("RETURN 'Jane' as name, 42 as age, null as email");
+    let persons: Vec<Person> = client.query(&query).await?.into_typed()?;
+
+    assert_eq!(
+        persons,
+        vec![Person { name: String::from("Jane"), age: 42, email: None }]);
+
+    // a missing field is an error:
+    let query = Query::new("RETURN 'Jane' as name");
+    let result: Result<Vec<Person>, _> = client.query(&query).await?.into_typed();
+    assert!(matches!(result, Err(ClientError::MissingRecordField(f)) if f == "age"));
+
+    // so is a value of another type, also for optional fields:
+    let query = Query::new("RETURN 'Jane' as name, 'old' as age");
+    let result: Result<Vec<Person>, _> = client.query(&query).await?.into_typed();
+    assert!(matches!(
+        result,
+        Err(ClientError::ConversionError(ConversionError::InvalidValue { field, .. })) if field == "age"));
+
+    let query = Query::new("RETURN 'Jane' as name, 42 as age, 1 as email");
+    let result: Result<Vec<Person>, _> = client.query(&query).await?.into_typed();
+    assert!(matches!(
+        result,
+        Err(ClientError::ConversionError(ConversionError::InvalidValue { field, .. })) if field == "email"));
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn query_into_tuples() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let query = Query::new("UNWIND [1, 2] as age RETURN 'Jane' as name, age");
+    let result = client.query(&query).await?;
+
+    let first = result.records().first().expect("Expected at least one result");
+    assert_eq!(first.get::<String>(0), Some(&String::from("Jane")));
+    assert_eq!(first.get::<i64>(1), Some(&1));
+    assert_eq!(first.get::<i64>(2), None);
+
+    let rows: Vec<(String, i64)> = result.into_typed()?;
+    assert_eq!(rows, vec![(String::from("Jane"), 1), (String::from("Jane"), 2)]);
+
+    Ok(())
+}
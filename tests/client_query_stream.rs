@@ -0,0 +1,74 @@
+use futures::StreamExt;
+use raio::client::{Client, ClientConfig};
+use raio::client::auth::Basic;
+use raio::client::error::ClientError;
+use raio::messaging::query::Query;
+use raio::messaging::request::Amount;
+
+#[async_std::test]
+pub async fn query_stream_batches() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0")
+                .fetch_size(Amount::Many(10)));
+
+    let mut query = Query::new("UNWIND range(1, $n) as x RETURN x");
+    query.param("n", 25);
+
+    // pull in batches of 10, which needs three PULLs:
+    let mut stream = client.query_stream(&query).await?;
+
+    let mut expected = 1;
+    while let Some(record) = stream.next().await {
+        assert_eq!(record?.get_field_typed("x"), Some(&expected));
+        expected += 1;
+    }
+
+    assert_eq!(expected, 26);
+    assert!(stream.is_finished());
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn query_stream_discard() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0")
+                .fetch_size(Amount::Many(10)));
+
+    let mut stream = client.query_stream(&Query::new("UNWIND range(1, 1000) as x RETURN x")).await?;
+
+    // consume the first record only and discard the rest:
+    assert!(stream.next().await.is_some());
+    let bookmark = stream.discard().await?;
+    assert!(bookmark.is_some());
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn auto_commit_into_stream() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let result = client.query(&Query::new("UNWIND range(1, 10) as x RETURN x")).await?;
+    let sum: i64 =
+        result
+            .into_stream()
+            .map(|r| r.map(|record| *record.get_field_typed::<i64>("x").unwrap()))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .sum::<Result<i64, ClientError>>()?;
+
+    assert_eq!(sum, 55);
+    Ok(())
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "arrow")]
+
+use arrow::datatypes::DataType;
+use raio::client::{Client, ClientConfig};
+use raio::client::auth::Basic;
+use raio::messaging::query::Query;
+
+#[async_std::test]
+pub async fn to_record_batch() -> Result<(), Box<dyn std::error::Error>> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let query = Query::new("UNWIND range(1, 3) AS x RETURN x, x / 2.0 AS y, toString(x) AS s, x > 1 AS b, null AS n");
+    let batch = client.query(&query).await?.into_columns().to_record_batch()?;
+
+    assert_eq!(batch.num_rows(), 3);
+    let types: Vec<DataType> = batch.schema().fields().iter().map(|f| f.data_type().clone()).collect();
+    assert_eq!(types, vec![DataType::Int64, DataType::Float64, DataType::Utf8, DataType::Boolean, DataType::Null]);
+
+    Ok(())
+}
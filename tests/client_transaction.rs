@@ -24,15 +24,131 @@ pub async fn transaction_simple() -> Result<(), ClientError> {
 
     let res_1 = transaction.run(&query_1).await?;
     assert_eq!(
-        res_1.first().expect("At least one result in _1").get_field_typed("x"),
+        res_1.records().first().expect("At least one result in _1").get_field_typed("x"),
         Some(&45));
 
     let res_2 = transaction.run(&query_2).await?;
     assert_eq!(
-        res_2.first().expect("At least one result in _2").get_field_typed("y"),
+        res_2.records().first().expect("At least one result in _2").get_field_typed("y"),
         Some(&true));
 
     transaction.commit().await?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[async_std::test]
+pub async fn transaction_builder() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let mut transaction =
+        client
+            .transaction()
+            .with_timeout(std::time::Duration::from_secs(10))
+            .with_metadata("test", "transaction_builder")
+            .read_only()
+            .begin()
+            .await?;
+
+    let result = transaction.run(&Query::new("RETURN 1 as x")).await?;
+    assert_eq!(result.records()[0].get_field_typed("x"), Some(&1));
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn run_batch() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let rows = (0..25).map(|i| {
+        let mut row = std::collections::HashMap::new();
+        row.insert(String::from("id"), i);
+        row
+    });
+
+    let mut query = Query::new("CREATE (:RunBatchTest {id: row.id, tag: $tag})");
+    query.param("tag", "run_batch");
+    assert_eq!(client.run_batch(&query, rows, 10).await?, 25);
+
+    let count = client.query(&Query::new("MATCH (n:RunBatchTest) DELETE n RETURN count(n) as n")).await?;
+    assert_eq!(count.records()[0].get_field_typed("n"), Some(&25));
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn transaction_run_stream() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0")
+                .fetch_size(raio::messaging::request::Amount::Many(10)));
+
+    let mut transaction = client.begin(CommitPrepare::new()).await?;
+
+    let mut cursor = transaction.run_stream(&Query::new("UNWIND range(1, 25) as x RETURN x")).await?;
+    let mut sum = 0;
+    while let Some(record) = cursor.next().await {
+        sum += record?.get_field_typed::<i64>("x").copied().unwrap_or(0);
+    }
+    assert_eq!(sum, 325);
+    assert!(cursor.summary().is_some());
+
+    // the transaction is usable again once the cursor is gone:
+    let result = transaction.run(&Query::new("RETURN 1 as x")).await?;
+    assert_eq!(result.records()[0].get_field_typed("x"), Some(&1));
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn transaction_dropped() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0")
+                .max_connections(1));
+
+    let mut transaction = client.begin(CommitPrepare::new()).await?;
+    assert!(transaction.is_open());
+    transaction.run(&Query::new("CREATE (:TransactionDroppedTest)")).await?;
+    drop(transaction);
+
+    // the single connection is reset before its reuse, which rolls back the transaction:
+    let count = client.query(&Query::new("MATCH (n:TransactionDroppedTest) RETURN count(n) as n")).await?;
+    assert_eq!(count.records()[0].get_field_typed("n"), Some(&0));
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn transaction_commit_outcome() -> Result<(), ClientError> {
+    let client =
+        Client::create(
+            "localhost:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let mut transaction = client.begin(CommitPrepare::new()).await?;
+    transaction.execute(&Query::new("CREATE (:CommitOutcomeTest), (:CommitOutcomeTest)")).await?;
+    transaction.run(&Query::new("MATCH (n:CommitOutcomeTest) DELETE n")).await?;
+    let outcome = transaction.commit().await?;
+
+    assert!(outcome.bookmark.is_some());
+    assert_eq!(outcome.summary.counters.nodes_created, 2);
+    assert_eq!(outcome.summary.counters.nodes_deleted, 2);
+
+    Ok(())
+}
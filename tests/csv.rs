@@ -0,0 +1,58 @@
+use packs::Value;
+use packs::std_structs::StdStruct;
+use raio::client::csv::{write_csv, CsvOptions};
+use raio::client::record_result::RecordResult;
+use raio::messaging::response::Record;
+
+fn csv(fields: &[&str], rows: Vec<Vec<Value<StdStruct>>>, options: &CsvOptions) -> String {
+    let fields: Vec<String> = fields.iter().map(|f| String::from(*f)).collect();
+    let records = rows.into_iter().map(|data| Record { data }).collect();
+    let rows = RecordResult::from_results(&fields, records).unwrap();
+
+    let mut csv = Vec::new();
+    write_csv(&mut csv, &fields, &rows, options).unwrap();
+    String::from_utf8(csv).unwrap()
+}
+
+#[test]
+pub fn csv_quotes_special_characters() {
+    let written =
+        csv(
+            &["a", "b"],
+            vec![
+                vec![Value::from("line\nbreak"), Value::from("carriage\rreturn")],
+                vec![Value::from("\"quoted\""), Value::from("plain")],
+                vec![Value::from(""), Value::Null],
+            ],
+            &CsvOptions::default());
+
+    assert_eq!(
+        written,
+        "a,b\n\"line\nbreak\",\"carriage\rreturn\"\n\"\"\"quoted\"\"\",plain\n,\n");
+}
+
+#[test]
+pub fn csv_quotes_custom_delimiter() {
+    let written =
+        csv(
+            &["a;b", "c,d"],
+            vec![vec![Value::from("x;y"), Value::from("x,y")]],
+            &CsvOptions::default().delimiter(';'));
+
+    // only the configured delimiter needs quoting, in the header as well:
+    assert_eq!(written, "\"a;b\";c,d\n\"x;y\";x,y\n");
+}
+
+#[test]
+pub fn csv_nested_literals() {
+    let written =
+        csv(
+            &["list", "bytes"],
+            vec![vec![
+                Value::from(vec![Value::from("it's"), Value::from(1), Value::Null]),
+                Value::Bytes(vec![0x0A, 0xFF]),
+            ]],
+            &CsvOptions::default().header(false));
+
+    assert_eq!(written, "\"['it\\'s', 1, null]\",0AFF\n");
+}
@@ -89,3 +89,65 @@ pub async fn open_connection_query() -> Result<(), connection::ConnectionError>
 
     Ok(())
 }
+
+#[async_std::test]
+/// Checks the liveness of an authenticated connection by a `RESET` and a `RETURN 1`.
+pub async fn reset_and_ping() -> Result<(), connection::ConnectionError> {
+    let config = ConnectionConfig::default();
+    let mut connection = Connection::connect("localhost:7687", config).await?;
+    connection.handshake(config.supported_versions()).await?;
+    connection.auth_hello("integrationtest_raio", "0.2.0", "basic", "neo4j", "mastertest").await?;
+
+    connection.reset().await?;
+    connection.ping().await?;
+    assert_eq!(connection.state(), connection::State::Ready);
+
+    Ok(())
+}
+
+#[async_std::test]
+/// A failed query leaves the connection in the `Failed` state until it is recovered.
+pub async fn recover_after_failure() -> Result<(), connection::ConnectionError> {
+    let config = ConnectionConfig::default();
+    let mut connection = Connection::connect("localhost:7687", config).await?;
+    connection.handshake(config.supported_versions()).await?;
+    connection.auth_hello("integrationtest_raio", "0.2.0", "basic", "neo4j", "mastertest").await?;
+
+    let query = Query::new("THIS IS NOT CYPHER");
+    connection.send(&Run::new(&query)).await?;
+    assert!(connection.recv_success().await.is_err());
+    assert_eq!(connection.state(), connection::State::Failed);
+
+    connection.recover().await?;
+    connection.ping().await?;
+    assert_eq!(connection.state(), connection::State::Ready);
+
+    Ok(())
+}
+
+#[async_std::test]
+/// The handshake sends the magic preamble followed by the four proposed version ranges, each as
+/// `[0, range, minor, major]`.
+pub async fn handshake_bytes() -> Result<(), connection::ConnectionError> {
+    use async_std::io::{ReadExt, WriteExt};
+
+    let listener = async_std::net::TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?.to_string();
+    let server = async_std::task::spawn(async move {
+        let (mut stream, _) = listener.accept().await?;
+        let mut handshake = [0u8; 20];
+        stream.read_exact(&mut handshake).await?;
+        stream.write_all(&[0, 0, 4, 4]).await?;
+        Ok::<_, std::io::Error>(handshake)
+    });
+
+    let config = ConnectionConfig::default();
+    let mut connection = Connection::connect(&address, config).await?;
+    assert_eq!(connection.handshake(config.supported_versions()).await?, Version::new(4, 4));
+
+    assert_eq!(
+        server.await?,
+        [0x60, 0x60, 0xB0, 0x17, 0, 2, 4, 4, 0, 0, 1, 4, 0, 0, 0, 4, 0, 0, 0, 0]);
+
+    Ok(())
+}
@@ -0,0 +1,326 @@
+#![cfg(feature = "test-util")]
+
+use packs::{Dictionary, Value};
+use raio::client::{Client, ClientConfig};
+use raio::client::auth::Basic;
+use raio::client::auto_commit::AutoCommit;
+use raio::client::error::{AuthError, ClientError};
+use raio::client::routing::RoutingClient;
+use raio::connectivity::connection::ConnectionError;
+use raio::connectivity::version::Version;
+use raio::messaging::commit_prepare::CommitMode;
+use raio::messaging::query::Query;
+use raio::testing::{MockServer, Reply, RequestKind};
+
+#[async_std::test]
+pub async fn mock_auto_commit() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(Version::new(4, 1)).await?;
+    server
+        .expect(RequestKind::Hello, vec![Reply::success()])
+        .expect(RequestKind::Run, vec![Reply::fields(&["x"])])
+        .expect(RequestKind::Pull, vec![Reply::record(vec![Value::from(42)]), Reply::success()]);
+
+    let client =
+        Client::create(
+            &server.endpoint(),
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let result = client.query(&Query::new("RETURN 42 as x")).await?;
+    assert_eq!(result.records()[0].get_field_typed::<i64>("x"), Some(&42));
+    assert!(result.bookmark().is_none());
+
+    server.verify()?;
+    let received = server.received();
+    assert_eq!(received[1].kind, RequestKind::Run);
+    assert_eq!(received[1].fields[0], Value::from("RETURN 42 as x"));
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn mock_failure() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(Version::new(4, 1)).await?;
+    server
+        .expect(RequestKind::Hello, vec![Reply::success()])
+        .expect(RequestKind::Run, vec![Reply::failure("Neo.ClientError.Statement.SyntaxError", "Invalid input")])
+        .expect(RequestKind::Pull, vec![Reply::Ignored]);
+
+    let client =
+        Client::create(
+            &server.endpoint(),
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let error = match client.query(&Query::new("RETUR 1")).await {
+        Err(e) => e,
+        Ok(_) => panic!("Query should fail"),
+    };
+    assert_eq!(
+        error.neo4j_error().map(|e| e.code.as_str()),
+        Some("Neo.ClientError.Statement.SyntaxError"));
+    assert!(matches!(error, ClientError::ConnectionError(_)));
+
+    server.verify()?;
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn mock_rollback_failure() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(Version::new(4, 1)).await?;
+    server
+        .expect(RequestKind::Hello, vec![Reply::success()])
+        .expect(RequestKind::Begin, vec![Reply::success()])
+        .expect(RequestKind::RollBack, vec![Reply::failure("Neo.DatabaseError.General.UnknownError", "Rollback failed")]);
+
+    let client =
+        Client::create(
+            &server.endpoint(),
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let transaction = client.begin(raio::messaging::commit_prepare::CommitPrepare::new()).await?;
+    let error = match transaction.rollback().await {
+        Err(e) => e,
+        Ok(_) => panic!("Rollback should fail"),
+    };
+    assert_eq!(
+        error.neo4j_error().map(|e| e.code.as_str()),
+        Some("Neo.DatabaseError.General.UnknownError"));
+
+    server.verify()?;
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn mock_failed_transaction() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(Version::new(4, 1)).await?;
+    server
+        .expect(RequestKind::Hello, vec![Reply::success()])
+        .expect(RequestKind::Begin, vec![Reply::success()])
+        .expect(RequestKind::Run, vec![Reply::failure("Neo.ClientError.Statement.SyntaxError", "Invalid input")]);
+
+    let client =
+        Client::create(
+            &server.endpoint(),
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let mut transaction = client.begin(raio::messaging::commit_prepare::CommitPrepare::new()).await?;
+    assert!(transaction.run(&Query::new("RETUR 1")).await.is_err());
+    assert!(!transaction.is_open());
+    assert!(matches!(transaction.run(&Query::new("RETURN 1")).await, Err(ClientError::TransactionClosed)));
+
+    server.verify()?;
+    let received = server.received();
+    assert_eq!(received.len(), 3);
+    assert!(received.iter().all(|r| r.kind != RequestKind::Reset));
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn mock_invalid_credentials() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(Version::new(4, 1)).await?;
+    server.expect(RequestKind::Hello, vec![Reply::failure("Neo.ClientError.Security.Unauthorized", "Wrong password")]);
+
+    let client =
+        Client::create(
+            &server.endpoint(),
+            Basic::new("neo4j", "wrong"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let error = match client.query(&Query::new("RETURN 1")).await {
+        Err(e) => e,
+        Ok(_) => panic!("Authentication should fail"),
+    };
+    assert!(matches!(
+        error,
+        ClientError::ConnectionError(ConnectionError::AuthenticationError(AuthError::InvalidCredentials(_)))));
+    assert!(!error.is_retryable());
+
+    server.verify()?;
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn mock_token_expired() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(Version::new(4, 1)).await?;
+    server
+        .expect(RequestKind::Hello, vec![Reply::success()])
+        .expect(RequestKind::Run, vec![Reply::failure("Neo.ClientError.Security.TokenExpired", "Token expired")])
+        .expect(RequestKind::Pull, vec![Reply::Ignored])
+        .expect(RequestKind::Hello, vec![Reply::success()])
+        .expect(RequestKind::Run, vec![Reply::fields(&["x"])])
+        .expect(RequestKind::Pull, vec![Reply::record(vec![Value::from(1)]), Reply::success()]);
+
+    let client =
+        Client::create(
+            &server.endpoint(),
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let result = client.query(&Query::new("RETURN 1 as x")).await?;
+    assert_eq!(result.records().len(), 1);
+
+    server.verify()?;
+    Ok(())
+}
+
+fn routing_table(router: &str, writer: &str) -> Reply {
+    routing_table_with(&[router], router, writer, 300)
+}
+
+fn routing_table_with(routers: &[&str], reader: &str, writer: &str, ttl: i64) -> Reply {
+    let server = |role: &str, addresses: &[&str]| {
+        let mut server = Dictionary::new();
+        server.add_property("role", role);
+        server.add_property("addresses", addresses.iter().map(|a| Value::from(*a)).collect::<Vec<_>>());
+        Value::Dictionary(server)
+    };
+
+    let mut rt = Dictionary::new();
+    rt.add_property("ttl", ttl);
+    rt.add_property("servers", vec![server("ROUTE", routers), server("READ", &[reader]), server("WRITE", &[writer])]);
+
+    let mut metadata = Dictionary::new();
+    metadata.add_property("rt", Value::Dictionary(rt));
+    Reply::Success(metadata)
+}
+
+#[async_std::test]
+pub async fn mock_routing_not_a_leader() -> Result<(), Box<dyn std::error::Error>> {
+    let router = MockServer::start(Version::new(4, 3)).await?;
+    let leader = MockServer::start(Version::new(4, 3)).await?;
+    router
+        .expect(RequestKind::Hello, vec![Reply::success()])
+        .expect(RequestKind::Route, vec![routing_table(&router.endpoint(), &router.endpoint())])
+        .expect(RequestKind::Run, vec![Reply::failure("Neo.ClientError.Cluster.NotALeader", "No longer the leader")])
+        .expect(RequestKind::Pull, vec![Reply::Ignored])
+        .expect(RequestKind::Route, vec![routing_table(&router.endpoint(), &leader.endpoint())]);
+    leader
+        .expect(RequestKind::Hello, vec![Reply::success()])
+        .expect(RequestKind::Run, vec![Reply::fields(&["x"])])
+        .expect(RequestKind::Pull, vec![Reply::record(vec![Value::from(1)]), Reply::success()]);
+
+    let client =
+        RoutingClient::create(
+            &router.endpoint(),
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let result = client.query(&Query::new("CREATE () RETURN 1 as x")).await?;
+    assert_eq!(result.records().len(), 1);
+    assert_eq!(client.routing_table().await?.writers, vec![leader.endpoint()]);
+
+    router.verify()?;
+    leader.verify()?;
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn mock_routing_failover() -> Result<(), Box<dyn std::error::Error>> {
+    let first = MockServer::start(Version::new(4, 3)).await?;
+    let second = MockServer::start(Version::new(4, 3)).await?;
+    let routers = [first.endpoint(), second.endpoint()];
+    let routers: Vec<&str> = routers.iter().map(String::as_str).collect();
+
+    // the first table expires right away, refreshing it fails on the first router:
+    first
+        .expect(RequestKind::Hello, vec![Reply::success()])
+        .expect(RequestKind::Route, vec![routing_table_with(&routers, routers[0], routers[0], 0)])
+        .expect(RequestKind::Run, vec![Reply::fields(&["x"])])
+        .expect(RequestKind::Pull, vec![Reply::record(vec![Value::from(1)]), Reply::success()])
+        .expect(RequestKind::Route, vec![Reply::failure("Neo.TransientError.General.DatabaseUnavailable", "Unavailable")]);
+    second
+        .expect(RequestKind::Hello, vec![Reply::success()])
+        .expect(RequestKind::Route, vec![routing_table_with(&routers[1..], routers[1], routers[1], 300)])
+        .expect(RequestKind::Run, vec![Reply::fields(&["x"])])
+        .expect(RequestKind::Pull, vec![Reply::record(vec![Value::from(2)]), Reply::success()]);
+
+    let client =
+        RoutingClient::create(
+            &first.endpoint(),
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let result = client.query(&Query::new("RETURN 1 as x")).await?;
+    assert_eq!(result.records()[0].get_field_typed::<i64>("x"), Some(&1));
+
+    let result = client.query(&Query::new("RETURN 2 as x")).await?;
+    assert_eq!(result.records()[0].get_field_typed::<i64>("x"), Some(&2));
+    assert_eq!(client.routing_table().await?.routers, vec![second.endpoint()]);
+
+    first.verify()?;
+    second.verify()?;
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn mock_access_mode_override() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(Version::new(4, 1)).await?;
+    server
+        .expect(RequestKind::Hello, vec![Reply::success()])
+        .expect(RequestKind::Run, vec![Reply::fields(&["x"])])
+        .expect(RequestKind::Pull, vec![Reply::record(vec![Value::from(1)]), Reply::success()])
+        .expect(RequestKind::Run, vec![Reply::fields(&["x"])])
+        .expect(RequestKind::Pull, vec![Reply::record(vec![Value::from(1)]), Reply::success()]);
+
+    let client =
+        Client::create(
+            &server.endpoint(),
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    let read = Query::new("MATCH (n) RETURN count(n) as x");
+    let write = Query::new("CREATE () RETURN 1 as x");
+    let mut session = client.session().mode(CommitMode::Read);
+    session.run(&read).await?;
+    let mut auto_commit = AutoCommit::new(&write);
+    auto_commit.write();
+    session.run_with(auto_commit).await?;
+
+    server.verify()?;
+    let modes: Vec<Option<String>> =
+        server
+            .received()
+            .into_iter()
+            .filter(|r| r.kind == RequestKind::Run)
+            .map(|r| match &r.fields[2] {
+                Value::Dictionary(extra) => extra.get_property_typed::<String>("mode").cloned(),
+                _ => None,
+            })
+            .collect();
+    assert_eq!(modes, vec![Some(String::from("r")), Some(String::from("w"))]);
+
+    Ok(())
+}
+
+#[async_std::test]
+pub async fn mock_connect_by_hostname() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(Version::new(4, 1)).await?;
+    server
+        .expect(RequestKind::Hello, vec![Reply::success()])
+        .expect(RequestKind::Run, vec![Reply::fields(&["x"])])
+        .expect(RequestKind::Pull, vec![Reply::record(vec![Value::from(1)]), Reply::success()]);
+
+    // `localhost` might resolve to `::1` first, where the server does not listen:
+    let port = server.endpoint().rsplit(':').next().unwrap_or_default().to_string();
+    let client =
+        Client::create(
+            &format!("localhost:{}", port),
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+
+    client.query(&Query::new("RETURN 1 as x")).await?;
+    server.verify()?;
+
+    let unresolvable =
+        Client::create(
+            "raio-rs-test.invalid:7687",
+            Basic::new("neo4j", "mastertest"),
+            ClientConfig::default("raio-rs-test", "0.2.0"));
+    assert!(unresolvable.query(&Query::new("RETURN 1 as x")).await.is_err());
+
+    Ok(())
+}